@@ -1,9 +1,27 @@
-use std::time::Duration;
+use std::{
+    io::ErrorKind,
+    net::{ToSocketAddrs, UdpSocket},
+    path::Path,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
 
-use reqwest::header::USER_AGENT;
+use reqwest::{StatusCode, header::USER_AGENT};
 
-use super::default;
-use crate::{Movie, Response, Torrent, client::Filter};
+use super::default::{self, Fetched};
+use crate::{
+    InfoHash, Match, Movie, Response, Selection, SelectorProfile, Torrent, TorrentPolicy,
+    client::{
+        Cache, CacheKind, CacheStats, Cassette, Crawl, CrawlLimits, DiskCache, DownloadedTorrent,
+        Endpoint, Filter, Observer, PostFilter, Proxies, RateLimiter, Scrape, ScrapeLimits,
+        SelfTest, SwarmHealth,
+        disk_cache::DiskEntry,
+        download, imdb,
+        scrape::{self, Tracker},
+        self_test::SELF_TEST_QUERY,
+    },
+    trace,
+};
 
 /// Client for interacting with the YTS movie API.
 ///
@@ -26,20 +44,113 @@ use crate::{Movie, Response, Torrent, client::Filter};
 /// # }
 /// # }
 /// ```
-#[derive(Debug, Default)]
-pub struct Yts<'a> {
-    inner: default::Yts<'a>,
+#[derive(Debug, Default, Clone)]
+pub struct Yts {
+    inner: default::Yts,
+    /// HTTP client, built on first use and shared with clones of this client.
+    http: Arc<OnceLock<reqwest::blocking::Client>>,
 }
 
 #[allow(dead_code)]
-impl<'a> Yts<'a> {
-    /// Creates a default `Yts` client with the official host and a 10-second timeout.
-    pub fn new(host: &'a str, timeout: Duration) -> Self {
+impl Yts {
+    /// Creates a new `Yts` client with a custom host and timeout.
+    ///
+    /// The host can be a `&str`, `String` or `Url`.
+    pub fn new(host: impl Into<String>, timeout: Duration) -> Self {
         Self {
             inner: default::Yts::new(host, timeout),
+            http: Arc::default(),
+        }
+    }
+
+    /// Parses pages with `selectors` instead of the standard YTS profile.
+    pub fn with_selectors(self, selectors: SelectorProfile) -> Self {
+        Self {
+            inner: self.inner.with_selectors(selectors),
+            ..self
+        }
+    }
+
+    /// Returns the base URL of the host.
+    pub fn host(&self) -> &str {
+        self.inner.host()
+    }
+
+    /// Enables caching of fetched pages, keyed by URL.
+    ///
+    /// Pass an `Arc<Cache>` to share one cache between several clients.
+    pub fn with_cache(self, cache: impl Into<Arc<Cache>>) -> Self {
+        Self {
+            inner: self.inner.with_cache(cache),
+            ..self
+        }
+    }
+
+    /// Enables the persistent disk cache.
+    ///
+    /// Pages found on disk are revalidated with conditional requests, or served without
+    /// any request when the disk cache is in cache-only mode.
+    pub fn with_disk_cache(self, disk_cache: DiskCache) -> Self {
+        Self {
+            inner: self.inner.with_disk_cache(disk_cache),
+            ..self
+        }
+    }
+
+    /// Limits the rate of requests sent to the website.
+    ///
+    /// The limiter is shared with clones of this client. Pass an `Arc<RateLimiter>` to share
+    /// it with other clients too.
+    pub fn with_rate_limiter(self, rate_limiter: impl Into<Arc<RateLimiter>>) -> Self {
+        Self {
+            inner: self.inner.with_rate_limiter(rate_limiter),
+            ..self
+        }
+    }
+
+    /// Routes requests through proxies, replacing those of the environment variables.
+    pub fn with_proxies(self, proxies: Proxies) -> Self {
+        Self {
+            inner: self.inner.with_proxies(proxies),
+            http: Arc::default(),
         }
     }
 
+    /// Reports requests, cache hits and parse failures to `observer`, e.g. to export metrics.
+    ///
+    /// The observer is shared with clones of this client.
+    pub fn with_observer(self, observer: impl Observer + 'static) -> Self {
+        Self {
+            inner: self.inner.with_observer(observer),
+            ..self
+        }
+    }
+
+    /// Records responses to, or replays them from, a cassette.
+    ///
+    /// When replaying, no request reaches the network.
+    pub fn with_cassette(self, cassette: Cassette) -> Self {
+        Self {
+            inner: self.inner.with_cassette(cassette),
+            ..self
+        }
+    }
+
+    /// Returns a copy of this client that skips cache lookups.
+    ///
+    /// Pages fetched through the copy still refresh the shared cache.
+    pub fn bypass_cache(&self) -> Self {
+        Self {
+            inner: self.inner.bypass_cache(),
+            http: Arc::clone(&self.http),
+        }
+    }
+
+    /// Returns the cache statistics, or `None` if caching is not enabled.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.inner.cache_stats()
+    }
+
     /// Searches for movies by name applying the specified filter options.
     ///
     /// # Parameters
@@ -48,19 +159,23 @@ impl<'a> Yts<'a> {
     ///
     /// # Returns
     /// A `Result` containing a `Response` with movies and pagination info, or an error.
+    /// For a custom [`Year::Range`](crate::Year::Range), the pagination info describes
+    /// the covering preset, see [`Year`](crate::Year).
     ///
     /// # Errors
-    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    /// Returns an error if the HTTP request fails, the year filter is invalid, or the
+    /// response cannot be parsed.
     pub fn search_with_filter(&self, movie_name: &str, filter: Filter) -> crate::Result<Response> {
-        let client = reqwest::blocking::Client::new();
+        let html = self.fetch(
+            &self.inner.create_url(movie_name, &filter)?,
+            Endpoint::Browse,
+        )?;
 
-        let response = client
-            .get(self.inner.create_url(movie_name, &filter)?)
-            .header(USER_AGENT, "Mozilla/5.0 (Linux x86_64)")
-            .timeout(self.inner.timeout)
-            .send()?;
+        let mut response = Response::create(&html, filter.page, &self.inner.selectors)
+            .inspect_err(|e| self.inner.parse_failed(Endpoint::Browse, e))?;
+        filter.apply_to(&mut response);
 
-        Response::create(&response.text()?, filter.page)
+        Ok(response)
     }
 
     /// Searches for movies by name using default filter parameters.
@@ -77,6 +192,205 @@ impl<'a> Yts<'a> {
         self.search_with_filter(movie_name, crate::Filters::default().build())
     }
 
+    /// Searches page after page until `limit` movies pass the post filter.
+    ///
+    /// Starts at the page set in `filter` and stops at the last page. When the post
+    /// filter has torrent criteria, the torrents of each candidate movie are fetched too.
+    ///
+    /// # Parameters
+    /// - `movie_name`: The name or keyword to search for.
+    /// - `filter`: A `Filter` struct specifying search filters (quality, genre, etc.).
+    /// - `post_filter`: Client-side criteria movies must meet.
+    /// - `limit`: Maximum number of movies to return.
+    ///
+    /// # Returns
+    /// A `Result` containing up to `limit` matching movies, or an error.
+    ///
+    /// # Errors
+    /// Returns an error if any HTTP request fails or a response cannot be parsed.
+    pub fn search_until(
+        &self,
+        movie_name: &str,
+        mut filter: Filter,
+        post_filter: &PostFilter,
+        limit: usize,
+    ) -> crate::Result<Vec<Movie>> {
+        let mut movies = Vec::new();
+
+        while movies.len() < limit {
+            let mut response = self.search_with_filter(movie_name, filter.clone())?;
+            post_filter.apply(&mut response);
+
+            for movie in response.movies {
+                if movies.len() == limit {
+                    break;
+                }
+                if post_filter.needs_torrents()
+                    && !post_filter.matches_torrents(&self.torrents(&movie)?)
+                {
+                    continue;
+                }
+                movies.push(movie);
+            }
+
+            match response.page.next_filter(&filter) {
+                Some(next) => filter = next,
+                None => break,
+            }
+        }
+
+        Ok(movies)
+    }
+
+    /// Checks that the parsers still understand the site, e.g. after a redesign.
+    ///
+    /// Searches a well-known title, then reads the torrents of the first movie found,
+    /// bypassing the cache. Failures are reported in the returned [`SelfTest`] rather than
+    /// as an error.
+    pub fn self_test(&self) -> SelfTest {
+        let yts = self.bypass_cache();
+        let response = yts.search(SELF_TEST_QUERY);
+        let torrents = response
+            .as_ref()
+            .ok()
+            .and_then(|r| r.movies.first())
+            .map(|movie| yts.torrents(movie).map(|torrents| torrents.len()));
+
+        SelfTest {
+            browse: response.map(|response| response.movies.len()),
+            torrents,
+        }
+    }
+
+    /// Searches every page of the results, merging them into one deduplicated response.
+    ///
+    /// Starts at the page set in `filter`, then fetches the following pages up to the last
+    /// one or the page limit, several at a time. Movies already listed by an earlier page
+    /// are dropped, and pages that fail are reported in the statistics. An ascending
+    /// direction the site cannot serve applies to the merged movies, see
+    /// [`Direction`](crate::Direction).
+    ///
+    /// # Parameters
+    /// - `movie_name`: The name or keyword to search for.
+    /// - `filter`: A `Filter` struct specifying search filters (quality, genre, etc.).
+    /// - `limits`: Bounds on the number of pages and concurrent requests.
+    ///
+    /// # Returns
+    /// A `Result` containing the merged `Response` and crawl statistics, or an error.
+    ///
+    /// # Errors
+    /// Returns an error if the first page cannot be fetched or parsed.
+    pub fn search_all(
+        &self,
+        movie_name: &str,
+        filter: Filter,
+        limits: CrawlLimits,
+    ) -> crate::Result<Crawl> {
+        let (filter, reverse) = filter.for_crawl();
+        let first = self.search_with_filter(movie_name, filter.clone())?;
+        let pages = limits.remaining(&first.page);
+        let last = (*pages.end()).max(first.page.current);
+
+        let pages = Mutex::new(pages);
+        let others = Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for _ in 0..limits.concurrency {
+                scope.spawn(|| {
+                    // Take the page in its own statement, releasing the lock while fetching.
+                    loop {
+                        let Some(page) = lock(&pages).next() else {
+                            break;
+                        };
+                        let filter = Filter {
+                            page,
+                            ..filter.clone()
+                        };
+                        let response = self.search_with_filter(movie_name, filter);
+                        lock(&others).push((page, response));
+                    }
+                });
+            }
+        });
+
+        let mut crawl = Crawl::merge(first, lock(&others).drain(..).collect(), last);
+        if reverse {
+            crawl.response.movies.reverse();
+        }
+        Ok(crawl)
+    }
+
+    /// Finds the movie best matching a title and release year, e.g. from an import list.
+    ///
+    /// Titles are compared without case, accents, punctuation or articles, and with roman
+    /// numerals as numbers, so `Godfather Part 2` matches `The Godfather Part II`. Release
+    /// years may differ by one, at a lower confidence.
+    ///
+    /// # Parameters
+    /// - `title`: The title of the movie.
+    /// - `year`: The release year of the movie.
+    ///
+    /// # Returns
+    /// A `Result` containing the best `Match`, or `None` if no candidate is close enough.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub fn find_best_match(&self, title: &str, year: u32) -> crate::Result<Option<Match>> {
+        let filter = crate::Filters::default().year(Match::years(year)).build();
+        let response = self.search_with_filter(title, filter)?;
+
+        Ok(Match::best(title, year, response.movies))
+    }
+
+    /// Looks up a movie by its IMDb title id, e.g. `tt0068646`.
+    ///
+    /// The search results are checked in order, returning the first one whose movie page
+    /// links to the IMDb title. Pass the movie to [`torrents`](Self::torrents) for its
+    /// details.
+    ///
+    /// # Parameters
+    /// - `id`: The IMDb title id, in any case.
+    ///
+    /// # Returns
+    /// A `Result` containing the matching `Movie`, or an error.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidImdbId`](crate::Error::InvalidImdbId) if `id` is malformed,
+    /// [`Error::MovieNotFound`](crate::Error::MovieNotFound) if no result links to the IMDb
+    /// title, or an error if an HTTP request fails or a page cannot be parsed.
+    pub fn by_imdb_id(&self, id: &str) -> crate::Result<Movie> {
+        let id = imdb::imdb_id(id)?;
+        let response = self.search(&id)?;
+
+        for movie in response.movies {
+            let html = self.fetch(&movie.link, Endpoint::Movie)?;
+            let linked = imdb::links_to(&html, &id, &self.inner.selectors)
+                .inspect_err(|e| self.inner.parse_failed(Endpoint::Movie, e))?;
+            if linked {
+                return Ok(movie);
+            }
+        }
+        Err(crate::Error::MovieNotFound(id))
+    }
+
+    /// Looks up several movies by their IMDb title ids, one after the other.
+    ///
+    /// # Parameters
+    /// - `ids`: The IMDb title ids.
+    ///
+    /// # Returns
+    /// The result of [`by_imdb_id`](Self::by_imdb_id) for each id, in the same order.
+    pub fn by_imdb_ids<I>(&self, ids: I) -> Vec<crate::Result<Movie>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut movies = Vec::new();
+        for id in ids {
+            movies.push(self.by_imdb_id(id.as_ref()));
+        }
+        movies
+    }
+
     /// Retrieves torrent information for a given movie.
     ///
     /// # Parameters
@@ -88,31 +402,432 @@ impl<'a> Yts<'a> {
     /// # Errors
     /// Returns an error if the HTTP request fails or the response cannot be parsed.
     pub fn torrents(&self, movie: &Movie) -> crate::Result<Vec<Torrent>> {
-        let client = reqwest::blocking::Client::new();
+        let html = self.fetch(&movie.link, Endpoint::Movie)?;
+        Torrent::create(&html, &self.inner.selectors)
+            .inspect_err(|e| self.inner.parse_failed(Endpoint::Movie, e))
+    }
+
+    /// Picks the best torrent of a movie according to `policy`.
+    ///
+    /// # Parameters
+    /// - `movie`: Reference to a `Movie` struct.
+    /// - `policy`: Rules ranking and rejecting torrents.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Selection`, with the best torrent if any and the reasons
+    /// each other torrent was rejected.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub fn best_torrent(&self, movie: &Movie, policy: &TorrentPolicy) -> crate::Result<Selection> {
+        let torrents = self.torrents(movie)?;
+
+        Ok(Torrent::select_best(&torrents, policy))
+    }
+
+    /// Downloads the `.torrent` file of a torrent into `dir`.
+    ///
+    /// The file is named after the movie title, year and quality, made safe for the
+    /// filesystem, and never overwrites an existing file. Torrent files bypass the caches
+    /// and cassette.
+    ///
+    /// # Parameters
+    /// - `movie`: Reference to the `Movie` the torrent belongs to.
+    /// - `torrent`: Reference to a `Torrent` struct of the movie.
+    /// - `dir`: Directory to save the file in, created if missing.
+    ///
+    /// # Returns
+    /// A `Result` containing the path of the saved file and the torrent info hash.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails, the body is not a valid torrent file or
+    /// the file cannot be written.
+    pub fn download_torrent(
+        &self,
+        movie: &Movie,
+        torrent: &Torrent,
+        dir: impl AsRef<Path>,
+    ) -> crate::Result<DownloadedTorrent> {
+        let url = &torrent.link;
+        let _permit = self
+            .inner
+            .rate_limiter
+            .as_ref()
+            .map(|rate_limiter| rate_limiter.acquire_blocking());
+
+        let started = Instant::now();
+        let (status, bytes) = self
+            .request_file(url, self.inner.timeout)
+            .inspect_err(|e| {
+                self.inner
+                    .observe(|observer| observer.on_request_error(Endpoint::Torrent, e))
+            })?;
+        self.inner.observe(|observer| {
+            observer.on_response(
+                Endpoint::Torrent,
+                status.as_u16(),
+                started.elapsed(),
+                bytes.len(),
+            )
+        });
+
+        self.inner.check_status(url, status)?;
+        download::save(&bytes, movie, torrent, dir.as_ref())
+    }
+
+    /// Asks trackers for the current swarm of a torrent.
+    ///
+    /// The seeds and peers shown on the site are cached and often stale, while trackers
+    /// report the peers announcing now. UDP trackers are scraped as described by BEP 15,
+    /// and HTTP trackers at their scrape URL, several at a time. Trackers are not the site,
+    /// so the caches, cassette and rate limiter do not apply, and UDP trackers are reached
+    /// without proxies. Unanswered UDP requests are resent until the tracker times out.
+    ///
+    /// # Parameters
+    /// - `info_hash`: Info hash of the torrent, e.g. from `download_torrent`.
+    /// - `trackers`: Tracker announce URLs, e.g. from
+    ///   [`TorrentFile::trackers`](crate::TorrentFile::trackers).
+    /// - `limits`: Time allowed to each tracker and number of trackers asked at once.
+    ///
+    /// # Returns
+    /// A `Scrape` with the answer or error of each tracker.
+    pub fn scrape(
+        &self,
+        info_hash: InfoHash,
+        trackers: &[impl AsRef<str> + Sync],
+        limits: ScrapeLimits,
+    ) -> Scrape {
+        let queue = Mutex::new(trackers.iter().enumerate());
+        let results = Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for _ in 0..limits.concurrency.min(trackers.len()) {
+                scope.spawn(|| {
+                    // Take the tracker in its own statement, releasing the lock while asking.
+                    loop {
+                        let Some((index, url)) = lock(&queue).next() else {
+                            break;
+                        };
+                        let url = url.as_ref();
+                        let health = self.scrape_tracker(url, &info_hash, limits.timeout);
+                        lock(&results).push((index, (url.to_string(), health)));
+                    }
+                });
+            }
+        });
+
+        Scrape::collect(info_hash, lock(&results).drain(..).collect())
+    }
+
+    /// Asks the tracker at `url` for the swarm of a torrent, within `timeout`.
+    fn scrape_tracker(
+        &self,
+        url: &str,
+        info_hash: &InfoHash,
+        timeout: Duration,
+    ) -> crate::Result<SwarmHealth> {
+        match Tracker::parse(url)? {
+            Tracker::Udp(addr) => scrape_udp(url, &addr, info_hash, timeout),
+            Tracker::Http(scrape_url) => {
+                let scrape_url = scrape::http_url(&scrape_url, info_hash);
+                let (status, body) =
+                    self.request_file(&scrape_url, timeout)
+                        .map_err(|e| match e {
+                            crate::Error::ReqwestError(e) if e.is_timeout() => {
+                                scrape::error(url, "timed out")
+                            }
+                            e => e,
+                        })?;
+                self.inner.check_status(&scrape_url, status)?;
+                scrape::http_scraped(url, &body, info_hash)
+            }
+        }
+    }
+
+    /// Fetches the body of a page, going through the cache when enabled.
+    ///
+    /// # Parameters
+    /// - `url`: URL of the page.
+    ///
+    /// # Returns
+    /// A `Result` containing the page body or an error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip(self),
+            fields(source, status, bytes, latency_ms),
+            err(level = "warn")
+        )
+    )]
+    fn fetch(&self, url: &str, endpoint: Endpoint) -> crate::Result<String> {
+        if let Some(body) = self.inner.cached(url) {
+            trace::record!(source = "memory", bytes = body.len());
+            self.inner
+                .observe(|observer| observer.on_cache_hit(endpoint, CacheKind::Memory));
+            return Ok(body);
+        }
 
-        let response = client
-            .get(&movie.link)
+        let body = match self.inner.load_stored(url)? {
+            Some(entry) if self.inner.is_cache_only() => {
+                trace::record!(source = "disk", bytes = entry.body.len());
+                self.inner
+                    .observe(|observer| observer.on_cache_hit(endpoint, CacheKind::Disk));
+                entry.body
+            }
+            stored => {
+                let fetched = match self.inner.replayed(url)? {
+                    Some(fetched) => {
+                        trace::record!(source = "cassette");
+                        fetched
+                    }
+                    None => {
+                        let _permit = self
+                            .inner
+                            .rate_limiter
+                            .as_ref()
+                            .map(|rate_limiter| rate_limiter.acquire_blocking());
+
+                        let started = Instant::now();
+                        let fetched = self.request(url, stored.as_ref()).inspect_err(|e| {
+                            self.inner
+                                .observe(|observer| observer.on_request_error(endpoint, e))
+                        })?;
+                        self.inner.responded(endpoint, &fetched, started.elapsed());
+                        self.inner.record(url, &fetched)?;
+                        fetched
+                    }
+                };
+                trace::record!(status = fetched.status.as_u16(), bytes = fetched.body.len());
+
+                self.inner.accept(url, endpoint, stored, fetched)?
+            }
+        };
+
+        self.inner.store(url, &body);
+
+        Ok(body)
+    }
+
+    /// Returns the shared HTTP client, building it on first use.
+    fn http_client(&self) -> crate::Result<&reqwest::blocking::Client> {
+        if let Some(client) = self.http.get() {
+            return Ok(client);
+        }
+
+        let mut client = reqwest::blocking::Client::builder();
+        if let Some(proxies) = self.inner.routing() {
+            client = client.proxy(proxies.to_reqwest());
+        }
+        let client = client.build()?;
+
+        Ok(self.http.get_or_init(|| client))
+    }
+
+    /// Sends a request for `url`, revalidating the `stored` page if any.
+    fn request(&self, url: &str, stored: Option<&DiskEntry>) -> crate::Result<Fetched> {
+        let response = self
+            .http_client()?
+            .get(url)
+            .headers(DiskEntry::conditional_headers(stored))
             .header(USER_AGENT, "Mozilla/5.0 (Linux x86_64)")
             .timeout(self.inner.timeout)
-            .send()?;
+            .send()
+            .map_err(|e| self.inner.request_error(url, e))?;
 
-        Torrent::create(&response.text()?)
+        Ok(Fetched {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.text()?,
+        })
+    }
+
+    /// Sends a request for the binary file at `url`, within `timeout`.
+    fn request_file(&self, url: &str, timeout: Duration) -> crate::Result<(StatusCode, Vec<u8>)> {
+        let response = self
+            .http_client()?
+            .get(url)
+            .header(USER_AGENT, "Mozilla/5.0 (Linux x86_64)")
+            .timeout(timeout)
+            .send()
+            .map_err(|e| self.inner.request_error(url, e))?;
+
+        Ok((response.status(), response.bytes()?.to_vec()))
+    }
+}
+
+/// Asks the UDP tracker at `addr` for the swarm of a torrent, as described by BEP 15,
+/// resending requests until answered or `timeout` elapses.
+fn scrape_udp(
+    url: &str,
+    addr: &str,
+    info_hash: &InfoHash,
+    timeout: Duration,
+) -> crate::Result<SwarmHealth> {
+    let deadline = Instant::now() + timeout;
+    let target = addr
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| scrape::error(url, "no address"))?;
+    let socket = UdpSocket::bind(scrape::local_addr(&target))?;
+    socket.connect(target)?;
+
+    let transaction_id = scrape::transaction_id();
+    let connection_id = udp_exchange(
+        url,
+        &socket,
+        &scrape::connect_request(transaction_id),
+        deadline,
+        |r| scrape::connected(url, r, transaction_id),
+    )?;
+
+    let transaction_id = scrape::transaction_id();
+    let request = scrape::scrape_request(connection_id, transaction_id, info_hash);
+    udp_exchange(url, &socket, &request, deadline, |r| {
+        scrape::scraped(url, r, transaction_id)
+    })
+}
+
+/// Sends `request` on `socket` until `read` accepts a response, resending it every
+/// [`scrape::RETRANSMIT`] until `deadline`. Responses `read` maps to `None` are ignored.
+fn udp_exchange<T>(
+    url: &str,
+    socket: &UdpSocket,
+    request: &[u8],
+    deadline: Instant,
+    read: impl Fn(&[u8]) -> crate::Result<Option<T>>,
+) -> crate::Result<T> {
+    let mut buffer = [0; scrape::MAX_PACKET];
+    loop {
+        socket.send(request)?;
+        let resend = (Instant::now() + scrape::RETRANSMIT).min(deadline);
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(scrape::error(url, "timed out"));
+            }
+            if now >= resend {
+                break;
+            }
+
+            socket.set_read_timeout(Some(resend - now))?;
+            match socket.recv(&mut buffer) {
+                Ok(length) => {
+                    if let Some(value) = read(&buffer[..length])? {
+                        return Ok(value);
+                    }
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
     }
 }
 
+/// Locks `mutex`, recovering the data if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 #[cfg(test)]
 mod test {
-    use crate::Filters;
+    use std::time::Duration;
+
+    use crate::{CrawlLimits, Filters, client::cassette};
 
     use super::Yts;
 
+    #[test]
+    fn test_blocking_client_is_shareable() {
+        fn assert_shareable<T: Send + Sync + Clone + 'static>() {}
+        assert_shareable::<Yts>();
+    }
+
+    #[test]
+    fn test_blocking_search_all() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let movies = ["Alien", "Aliens", "Alien 3"]
+            .map(|name| crate::test_util::movie(name, 1979, 8.5, vec![]));
+        for (page, listed) in [(1, [&movies[0], &movies[1]]), (2, [&movies[1], &movies[2]])] {
+            let filter = Filters::default().page(page).build();
+            server.browse("alien", &filter, 40, &listed);
+        }
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+
+        let crawl = yts
+            .search_all("alien", Filters::default().build(), CrawlLimits::default())
+            .unwrap();
+
+        assert_eq!(crawl.response.movies.len(), 3);
+        assert_eq!((crawl.stats.pages, crawl.stats.duplicates), (2, 1));
+    }
+
+    #[test]
+    fn test_blocking_search_all_concurrent() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let movie = crate::test_util::movie("Alien", 1979, 8.5, vec![]);
+        for page in 1..=4 {
+            let filter = Filters::default().page(page).build();
+            server.browse("alien", &filter, 80, &[&movie]);
+            if page > 1 {
+                let target = server.browse_target("alien", &filter).unwrap();
+                server.delay(&target, Duration::from_millis(300));
+            }
+        }
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+
+        let started = std::time::Instant::now();
+        let crawl = yts
+            .search_all(
+                "alien",
+                Filters::default().build(),
+                CrawlLimits::default().concurrency(3),
+            )
+            .unwrap();
+
+        assert_eq!(crawl.stats.pages, 4);
+        assert!(started.elapsed() < Duration::from_millis(800));
+    }
+
+    #[test]
+    fn test_blocking_scrape() {
+        let tracker = crate::test_util::FakeTracker::start().unwrap();
+        let silent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let info_hash = crate::InfoHash::from([7; 20]);
+        let health = crate::SwarmHealth {
+            seeders: 120,
+            leechers: 8,
+            completed: 4500,
+        };
+        tracker.swarm(info_hash, health);
+        let trackers = [
+            tracker.udp_url(),
+            format!("udp://{}/announce", silent.local_addr().unwrap()),
+            tracker.http_url(),
+        ];
+        let limits = crate::ScrapeLimits::default().timeout(Duration::from_millis(300));
+
+        let scrape = Yts::default().scrape(info_hash, &trackers, limits);
+
+        assert_eq!(scrape.trackers[0].1.as_ref().unwrap(), &health);
+        assert!(matches!(
+            &scrape.trackers[1].1,
+            Err(crate::Error::TrackerError(e)) if e.ends_with("timed out")
+        ));
+        assert_eq!(scrape.trackers[2].1.as_ref().unwrap(), &health);
+
+        tracker.lose_udp(1);
+        tracker.stray_udp(true);
+        let limits = limits.timeout(Duration::from_secs(2));
+        let scrape = Yts::default().scrape(info_hash, &trackers[..1], limits);
+        assert_eq!(scrape.trackers[0].1.as_ref().unwrap(), &health);
+    }
+
     #[test]
     fn test_blocking_search() {
-        let yts = Yts::default();
-        let results = yts.search_with_filter(
-            "godfather",
-            Filters::default().build(),
-        );
+        let yts = Yts::default().with_cassette(cassette::fixture("godfather"));
+        let results = yts.search_with_filter("godfather", Filters::default().build());
 
         assert!(results.is_ok());
         assert!(!results.as_ref().unwrap().movies.is_empty());
@@ -0,0 +1,223 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// In-memory cache of page bodies, keyed by the requested URL.
+///
+/// Entries expire after a time-to-live and the least recently used entry is evicted once
+/// the cache is full. A cache is safe to share between tasks and threads, and between
+/// clients when wrapped in an [`Arc`](std::sync::Arc).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yts_movies::{Cache, Yts};
+///
+/// let yts = Yts::default().with_cache(Cache::new(Duration::from_secs(300), 500));
+/// ```
+#[derive(Debug)]
+pub struct Cache {
+    /// How long an entry stays valid.
+    ttl: Duration,
+    /// Maximum number of entries kept.
+    max_entries: usize,
+    /// Entries and their recency order.
+    state: Mutex<CacheState>,
+    /// Number of lookups answered from the cache.
+    hits: AtomicU64,
+    /// Number of lookups that missed or found an expired entry.
+    misses: AtomicU64,
+}
+
+/// Hit/miss statistics of a [`Cache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of lookups answered from the cache.
+    pub hits: u64,
+    /// Number of lookups that missed or found an expired entry.
+    pub misses: u64,
+    /// Number of entries currently stored, including expired ones not yet evicted.
+    pub entries: usize,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Cached entries by URL.
+    entries: HashMap<String, CacheEntry>,
+    /// URLs by last use, oldest first.
+    recency: BTreeMap<u64, String>,
+    /// Monotonic counter used to order uses.
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    body: String,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+impl Cache {
+    /// Creates an empty cache.
+    ///
+    /// # Parameters
+    /// - `ttl`: How long an entry stays valid.
+    /// - `max_entries`: Maximum number of entries kept before evicting the least recently used.
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            state: Mutex::default(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the hit/miss statistics.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lock().entries.len(),
+        }
+    }
+
+    /// Removes every entry. Statistics are kept.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.recency.clear();
+    }
+
+    /// Returns the cached body for `url`, if present and not expired.
+    pub(crate) fn get(&self, url: &str) -> Option<String> {
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        let body = match state.entries.get_mut(url) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => {
+                state.recency.remove(&entry.last_used);
+                state.tick += 1;
+                entry.last_used = state.tick;
+                state.recency.insert(entry.last_used, url.to_string());
+                Some(entry.body.clone())
+            }
+            Some(entry) => {
+                state.recency.remove(&entry.last_used);
+                state.entries.remove(url);
+                None
+            }
+            None => None,
+        };
+
+        let counter = if body.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        body
+    }
+
+    /// Stores the body for `url`, evicting the least recently used entry if full.
+    pub(crate) fn insert(&self, url: &str, body: &str) {
+        if self.max_entries == 0 {
+            return;
+        }
+
+        let mut state = self.lock();
+        if let Some(entry) = state.entries.remove(url) {
+            state.recency.remove(&entry.last_used);
+        }
+
+        while state.entries.len() >= self.max_entries {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+
+        state.tick += 1;
+        let last_used = state.tick;
+        state.recency.insert(last_used, url.to_string());
+        state.entries.insert(
+            url.to_string(),
+            CacheEntry {
+                body: body.to_string(),
+                stored_at: Instant::now(),
+                last_used,
+            },
+        );
+    }
+
+    /// Locks the state, recovering it if another thread panicked while holding it.
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Cache, CacheStats};
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let cache = Cache::new(Duration::from_secs(60), 10);
+
+        assert_eq!(cache.get("https://yts.lt/a"), None);
+        cache.insert("https://yts.lt/a", "body a");
+        assert_eq!(cache.get("https://yts.lt/a").as_deref(), Some("body a"));
+
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                entries: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_cache_ttl() {
+        let cache = Cache::new(Duration::from_millis(20), 10);
+
+        cache.insert("https://yts.lt/a", "body a");
+        std::thread::sleep(Duration::from_millis(40));
+
+        assert_eq!(cache.get("https://yts.lt/a"), None);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_cache_lru_eviction() {
+        let cache = Cache::new(Duration::from_secs(60), 2);
+
+        cache.insert("https://yts.lt/a", "body a");
+        cache.insert("https://yts.lt/b", "body b");
+        assert!(cache.get("https://yts.lt/a").is_some());
+
+        cache.insert("https://yts.lt/c", "body c");
+        assert!(cache.get("https://yts.lt/a").is_some());
+        assert!(cache.get("https://yts.lt/b").is_none());
+        assert!(cache.get("https://yts.lt/c").is_some());
+
+        cache.insert("https://yts.lt/c", "body c2");
+        assert_eq!(cache.stats().entries, 2);
+        assert_eq!(cache.get("https://yts.lt/c").as_deref(), Some("body c2"));
+
+        cache.clear();
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
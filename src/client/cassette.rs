@@ -0,0 +1,279 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Name of the index file inside a cassette directory.
+const INDEX: &str = "cassette.txt";
+
+/// Recorded HTTP interactions used to run a client without the network.
+///
+/// A cassette is a directory holding one body file per request plus a `cassette.txt`
+/// index. Each index line is `<status> <file> <url>`; blank lines and lines starting with
+/// `#` are ignored. In [`CassetteMode::Record`] every response fetched from the network
+/// is written to the cassette; in [`CassetteMode::Replay`] responses are served from it
+/// and a request missing from the cassette fails with
+/// [`Error::CassetteError`](crate::Error::CassetteError).
+///
+/// # Examples
+///
+/// ```no_run
+/// use yts_movies::{Cassette, Yts};
+///
+/// # async fn example() -> yts_movies::Result {
+/// // Record once against the live site...
+/// let yts = Yts::default().with_cassette(Cassette::record("fixtures/godfather")?);
+/// yts.search("the godfather").await?;
+///
+/// // ...then replay deterministically.
+/// let yts = Yts::default().with_cassette(Cassette::replay("fixtures/godfather")?);
+/// let response = yts.search("the godfather").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Cassette {
+    /// Directory holding the index and body files.
+    dir: PathBuf,
+    /// Whether responses are recorded or replayed.
+    mode: CassetteMode,
+    /// Recorded interactions by URL.
+    entries: Mutex<HashMap<String, CassetteEntry>>,
+}
+
+/// Whether a [`Cassette`] records or replays responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Fetch from the network and write every response to the cassette.
+    Record,
+    /// Serve responses from the cassette, never touching the network.
+    Replay,
+}
+
+#[derive(Debug, Clone)]
+struct CassetteEntry {
+    /// HTTP status code of the response.
+    status: u16,
+    /// Body file name, relative to the cassette directory.
+    file: String,
+}
+
+impl Cassette {
+    /// Opens a cassette for replay.
+    ///
+    /// # Errors
+    /// Returns an error if the index cannot be read or has a malformed line.
+    pub fn replay(dir: impl Into<PathBuf>) -> crate::Result<Self> {
+        Self::open(dir.into(), CassetteMode::Replay)
+    }
+
+    /// Opens a cassette for recording, creating the directory if needed.
+    ///
+    /// Interactions already in the cassette are kept, and overwritten when the same URL
+    /// is fetched again.
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be created or the index cannot be read.
+    pub fn record(dir: impl Into<PathBuf>) -> crate::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        if !dir.join(INDEX).exists() {
+            fs::write(dir.join(INDEX), "")?;
+        }
+        Self::open(dir, CassetteMode::Record)
+    }
+
+    /// Returns whether the cassette records or replays.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Returns the cassette directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the recorded status and body for `url`.
+    ///
+    /// # Errors
+    /// Returns an error if `url` was not recorded or its body file cannot be read.
+    pub(crate) fn play(&self, url: &str) -> crate::Result<(u16, String)> {
+        let entry = self.lock().get(url).cloned().ok_or_else(|| {
+            crate::Error::CassetteError(format!("no recorded response for {url}"))
+        })?;
+
+        Ok((entry.status, fs::read_to_string(self.dir.join(entry.file))?))
+    }
+
+    /// Records the status and body fetched for `url`.
+    ///
+    /// # Errors
+    /// Returns an error if the body or the index cannot be written.
+    pub(crate) fn save(&self, url: &str, status: u16, body: &str) -> crate::Result {
+        let mut entries = self.lock();
+
+        let file = match entries.get(url) {
+            Some(entry) => entry.file.clone(),
+            None => (entries.len() + 1..)
+                .map(|n| format!("{n:03}.html"))
+                .find(|file| {
+                    !self.dir.join(file).exists()
+                        && entries.values().all(|entry| &entry.file != file)
+                })
+                .expect("file numbers are unbounded"),
+        };
+        fs::write(self.dir.join(&file), body)?;
+        entries.insert(url.to_string(), CassetteEntry { status, file });
+
+        let mut lines = entries
+            .iter()
+            .map(|(url, entry)| format!("{} {} {}", entry.status, entry.file, url))
+            .collect::<Vec<_>>();
+        lines.sort_by(|a, b| a.split(' ').nth(1).cmp(&b.split(' ').nth(1)));
+        fs::write(self.dir.join(INDEX), lines.join("\n") + "\n")?;
+
+        Ok(())
+    }
+
+    /// Reads the index of the cassette in `dir`.
+    fn open(dir: PathBuf, mode: CassetteMode) -> crate::Result<Self> {
+        let index = fs::read_to_string(dir.join(INDEX))?;
+
+        let mut entries = HashMap::new();
+        for line in index.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(3, ' ');
+            let (Some(status), Some(file), Some(url)) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(crate::Error::CassetteError(format!(
+                    "malformed line: {line}"
+                )));
+            };
+            let status = status
+                .parse()
+                .map_err(|_| crate::Error::CassetteError(format!("malformed status: {line}")))?;
+
+            entries.insert(
+                url.to_string(),
+                CassetteEntry {
+                    status,
+                    file: file.to_string(),
+                },
+            );
+        }
+
+        Ok(Self {
+            dir,
+            mode,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Locks the entries, recovering them if another thread panicked while holding them.
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CassetteEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Opens the committed fixture cassette `name` from `tests/fixtures`.
+///
+/// Set `YTS_RECORD=1` to re-record it against the live site instead.
+#[cfg(test)]
+pub(crate) fn fixture(name: &str) -> Cassette {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+
+    if std::env::var_os("YTS_RECORD").is_some() {
+        Cassette::record(dir).unwrap()
+    } else {
+        Cassette::replay(dir).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cassette, CassetteMode};
+
+    #[test]
+    fn test_cassette_record_and_replay() {
+        let dir = std::env::temp_dir().join(format!("yts-movies-cassette-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let cassette = Cassette::record(&dir).unwrap();
+        assert_eq!(cassette.mode(), CassetteMode::Record);
+        cassette.save("https://yts.lt/a", 200, "body a").unwrap();
+        cassette
+            .save("https://yts.lt/b?page=2", 404, "missing")
+            .unwrap();
+        cassette.save("https://yts.lt/a", 200, "body a2").unwrap();
+
+        let cassette = Cassette::replay(&dir).unwrap();
+        assert_eq!(cassette.mode(), CassetteMode::Replay);
+        assert_eq!(
+            cassette.play("https://yts.lt/a").unwrap(),
+            (200, "body a2".to_string())
+        );
+        assert_eq!(
+            cassette.play("https://yts.lt/b?page=2").unwrap(),
+            (404, "missing".to_string())
+        );
+        assert!(cassette.play("https://yts.lt/c").is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_cassette_save_keeps_existing_files() {
+        let dir = std::env::temp_dir().join(format!("yts-movies-numbering-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("cassette.txt"), "200 002.html https://yts.lt/a\n").unwrap();
+        std::fs::write(dir.join("002.html"), "body a").unwrap();
+        std::fs::write(dir.join("003.html"), "stray").unwrap();
+
+        let cassette = Cassette::record(&dir).unwrap();
+        cassette.save("https://yts.lt/b", 200, "body b").unwrap();
+
+        let cassette = Cassette::replay(&dir).unwrap();
+        assert_eq!(
+            cassette.play("https://yts.lt/a").unwrap(),
+            (200, "body a".to_string())
+        );
+        assert_eq!(
+            cassette.play("https://yts.lt/b").unwrap(),
+            (200, "body b".to_string())
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("003.html")).unwrap(),
+            "stray"
+        );
+        assert!(dir.join("004.html").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_cassette_malformed_index() {
+        let dir = std::env::temp_dir().join(format!("yts-movies-malformed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("cassette.txt"),
+            "# comment\n\nok 001.html https://yts.lt\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            Cassette::replay(&dir),
+            Err(crate::Error::CassetteError(_))
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
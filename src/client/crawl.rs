@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use crate::{Page, Response};
+
+/// Bounds of a `search_all` crawl.
+///
+/// By default every page is fetched, four at a time.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::CrawlLimits;
+///
+/// let limits = CrawlLimits::default().max_pages(10).concurrency(2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrawlLimits {
+    /// Maximum number of pages to fetch, including the first one.
+    pub(crate) max_pages: Option<u32>,
+    /// Maximum number of pages fetched at the same time.
+    pub(crate) concurrency: usize,
+}
+
+/// Statistics of a `search_all` crawl.
+#[derive(Debug, Default)]
+pub struct CrawlStats {
+    /// Number of pages fetched and parsed.
+    pub pages: u32,
+    /// Number of movies dropped because an earlier page already listed them, e.g. when
+    /// new releases shift results between pages during the crawl.
+    pub duplicates: usize,
+    /// Pages that could not be fetched or parsed, with their error.
+    pub failures: Vec<(u32, crate::Error)>,
+}
+
+/// Result of a `search_all` crawl.
+#[derive(Debug)]
+pub struct Crawl {
+    /// Movies of every page, deduplicated, in page order.
+    ///
+    /// The page info is that of the first page, with `next` pointing past the crawl.
+    pub response: Response,
+    /// Statistics of the crawl.
+    pub stats: CrawlStats,
+}
+
+impl Default for CrawlLimits {
+    fn default() -> Self {
+        Self {
+            max_pages: None,
+            concurrency: 4,
+        }
+    }
+}
+
+impl CrawlLimits {
+    /// Fetches at most `max_pages` pages, including the first one.
+    pub fn max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = Some(max_pages.max(1));
+        self
+    }
+
+    /// Fetches at most `concurrency` pages at the same time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Returns the pages left to fetch after the `first` one.
+    pub(crate) fn remaining(&self, first: &Page) -> std::ops::RangeInclusive<u32> {
+        let last = match self.max_pages {
+            Some(max_pages) => first.of.min(first.current.saturating_add(max_pages - 1)),
+            None => first.of,
+        };
+        first.current + 1..=last
+    }
+}
+
+impl Crawl {
+    /// Merges the `first` response with the `others` of the crawl, in any order.
+    pub(crate) fn merge(
+        first: Response,
+        others: Vec<(u32, crate::Result<Response>)>,
+        last: u32,
+    ) -> Self {
+        let mut stats = CrawlStats {
+            pages: 1,
+            ..CrawlStats::default()
+        };
+        let Response { page, mut movies } = first;
+
+        let mut others = others;
+        others.sort_by_key(|(page, _)| *page);
+
+        let mut seen = movies
+            .iter()
+            .map(|movie| movie.link.clone())
+            .collect::<HashSet<_>>();
+        for (number, response) in others {
+            match response {
+                Ok(response) => {
+                    stats.pages += 1;
+                    for movie in response.movies {
+                        if seen.insert(movie.link.clone()) {
+                            movies.push(movie);
+                        } else {
+                            stats.duplicates += 1;
+                        }
+                    }
+                }
+                Err(error) => stats.failures.push((number, error)),
+            }
+        }
+
+        Self {
+            response: Response {
+                page: Page {
+                    next: (last < page.of).then_some(last + 1),
+                    ..page
+                },
+                movies,
+            },
+            stats,
+        }
+    }
+}
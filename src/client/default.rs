@@ -1,13 +1,45 @@
-use std::time::Duration;
+use std::{
+    borrow::Cow,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
-use reqwest::{Url, header::USER_AGENT};
+use reqwest::{StatusCode, header::HeaderMap};
 
-use crate::{Movie, Response, Torrent, client::Filter};
+use crate::{
+    SelectorProfile,
+    client::{
+        Cache, CacheKind, CacheStats, Cassette, CassetteMode, DiskCache, Endpoint, Filter,
+        Observer, Proxies, Proxy, RateLimiter, disk_cache::DiskEntry,
+    },
+    trace,
+};
+
+#[cfg(feature = "async")]
+use std::{path::Path, time::Instant};
+
+#[cfg(feature = "async")]
+use reqwest::header::USER_AGENT;
+
+#[cfg(feature = "async")]
+use crate::{
+    InfoHash, Match, Movie, Response, Selection, Torrent, TorrentPolicy,
+    client::{
+        Crawl, CrawlLimits, DownloadedTorrent, PostFilter, Scrape, ScrapeLimits, SelfTest,
+        SwarmHealth, download, imdb,
+        scrape::{self, Tracker},
+        self_test::SELF_TEST_QUERY,
+    },
+};
 
 /// Client for interacting with the YTS movie API.
 ///
 /// Provides methods to search for movies and retrieve torrent information.
 ///
+/// The client owns its configuration and is `Send + Sync`. Clones are cheap and share the
+/// connection pool, caches, rate limiter and observer, so a client can be stored in an
+/// `Arc` or cloned into each task.
+///
 /// # Examples
 ///
 /// ```
@@ -22,37 +54,162 @@ use crate::{Movie, Response, Torrent, client::Filter};
 /// #   Ok(())
 /// # }
 /// ```
-#[derive(Debug)]
-pub struct Yts<'a> {
-    /// Base URL of the YTS API host.
-    pub(crate) host: &'a str,
+#[derive(Debug, Clone)]
+pub struct Yts {
+    /// Base URL of the YTS API host, without a trailing slash.
+    pub(crate) host: Arc<str>,
     /// Request timeout duration.
     pub(crate) timeout: Duration,
+    /// Optional cache of page bodies, shared by clones of the client.
+    pub(crate) cache: Option<Arc<Cache>>,
+    /// Whether cache lookups are skipped. Fresh bodies are still stored.
+    pub(crate) bypass_cache: bool,
+    /// Optional persistent cache of page bodies.
+    pub(crate) disk_cache: Option<DiskCache>,
+    /// Optional cassette recording or replaying responses.
+    pub(crate) cassette: Option<Arc<Cassette>>,
+    /// Optional rate limiter shared with clones of this client.
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    /// Optional proxy routing. The environment variables apply when unset.
+    pub(crate) proxies: Option<Proxies>,
+    /// Optional observer of requests, cache hits and parse failures.
+    pub(crate) observer: Option<Arc<dyn Observer>>,
+    /// Selectors used to parse pages.
+    pub(crate) selectors: SelectorProfile,
+    /// HTTP client, built on first use and shared with clones of this client.
+    http: Arc<OnceLock<reqwest::Client>>,
 }
 
-impl Default for Yts<'_> {
+/// A page as returned by the network or a cassette.
+#[derive(Debug)]
+pub(crate) struct Fetched {
+    /// HTTP status code.
+    pub(crate) status: StatusCode,
+    /// Response headers. Empty when replayed.
+    pub(crate) headers: HeaderMap,
+    /// Response body.
+    pub(crate) body: String,
+}
+
+impl Default for Yts {
     /// Creates a default `Yts` client with the official host and a 10-second timeout.
     fn default() -> Self {
-        Self {
-            host: "https://yts.lt",
-            timeout: Duration::from_secs(10),
-        }
+        Self::new("https://yts.lt", Duration::from_secs(10))
     }
 }
 
-impl<'a> Yts<'a> {
+impl Yts {
     /// Creates a new `Yts` client with a custom host and timeout.
     ///
     /// # Parameters
-    /// - `host`: Base URL of the YTS API.
+    /// - `host`: Base URL of the YTS API, as a `&str`, `String` or `Url`.
     /// - `timeout`: Duration before requests time out.
     ///
     /// # Returns
     /// A new instance of `Yts`.
-    pub fn new(host: &'a str, timeout: Duration) -> Self {
-        Self { host, timeout }
+    pub fn new(host: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            host: host.into().trim_end_matches('/').into(),
+            timeout,
+            cache: None,
+            bypass_cache: false,
+            disk_cache: None,
+            cassette: None,
+            rate_limiter: None,
+            proxies: None,
+            observer: None,
+            selectors: SelectorProfile::default(),
+            http: Arc::default(),
+        }
+    }
+
+    /// Enables caching of fetched pages, keyed by URL.
+    ///
+    /// Pass an `Arc<Cache>` to share one cache between several clients.
+    pub fn with_cache(mut self, cache: impl Into<Arc<Cache>>) -> Self {
+        self.cache = Some(cache.into());
+        self
     }
 
+    /// Enables the persistent disk cache.
+    ///
+    /// Pages found on disk are revalidated with conditional requests, or served without
+    /// any request when the disk cache is in cache-only mode.
+    pub fn with_disk_cache(mut self, disk_cache: DiskCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    /// Limits the rate of requests sent to the website.
+    ///
+    /// The limiter is shared with clones of this client. Pass an `Arc<RateLimiter>` to share
+    /// it with other clients too.
+    pub fn with_rate_limiter(mut self, rate_limiter: impl Into<Arc<RateLimiter>>) -> Self {
+        self.rate_limiter = Some(rate_limiter.into());
+        self
+    }
+
+    /// Routes requests through proxies, replacing those of the environment variables.
+    pub fn with_proxies(mut self, proxies: Proxies) -> Self {
+        self.proxies = Some(proxies);
+        self.http = Arc::default();
+        self
+    }
+
+    /// Parses pages with `selectors` instead of the standard YTS profile.
+    pub fn with_selectors(mut self, selectors: SelectorProfile) -> Self {
+        self.selectors = selectors;
+        self
+    }
+
+    /// Returns the base URL of the host.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Reports requests, cache hits and parse failures to `observer`, e.g. to export metrics.
+    ///
+    /// The observer is shared with clones of this client.
+    pub fn with_observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Records responses to, or replays them from, a cassette.
+    ///
+    /// When replaying, no request reaches the network.
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(Arc::new(cassette));
+        self
+    }
+
+    /// Returns a copy of this client that skips cache lookups.
+    ///
+    /// Pages fetched through the copy still refresh the shared cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn example(yts: yts_movies::Yts) -> yts_movies::Result {
+    /// let fresh = yts.bypass_cache().search("Inception").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bypass_cache(&self) -> Self {
+        Self {
+            bypass_cache: true,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the cache statistics, or `None` if caching is not enabled.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+}
+
+#[cfg(feature = "async")]
+impl Yts {
     /// Searches for movies by name applying the specified filter options.
     ///
     /// # Parameters
@@ -61,24 +218,26 @@ impl<'a> Yts<'a> {
     ///
     /// # Returns
     /// A `Result` containing a `Response` with movies and pagination info, or an error.
+    /// For a custom [`Year::Range`](crate::Year::Range), the pagination info describes
+    /// the covering preset, see [`Year`](crate::Year).
     ///
     /// # Errors
-    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    /// Returns an error if the HTTP request fails, the year filter is invalid, or the
+    /// response cannot be parsed.
     pub async fn search_with_filter(
         &self,
         movie_name: &str,
         filter: Filter,
     ) -> crate::Result<Response> {
-        let client = reqwest::Client::new();
-
-        let response = client
-            .get(self.create_url(movie_name, &filter)?)
-            .header(USER_AGENT, "Mozilla/5.0 (Linux x86_64)")
-            .timeout(self.timeout)
-            .send()
+        let html = self
+            .fetch(&self.create_url(movie_name, &filter)?, Endpoint::Browse)
             .await?;
 
-        Response::create(&response.text().await?, filter.page)
+        let mut response = Response::create(&html, filter.page, &self.selectors)
+            .inspect_err(|e| self.parse_failed(Endpoint::Browse, e))?;
+        filter.apply_to(&mut response);
+
+        Ok(response)
     }
 
     /// Searches for movies by name using default filter parameters.
@@ -96,6 +255,202 @@ impl<'a> Yts<'a> {
             .await
     }
 
+    /// Searches page after page until `limit` movies pass the post filter.
+    ///
+    /// Starts at the page set in `filter` and stops at the last page. When the post
+    /// filter has torrent criteria, the torrents of each candidate movie are fetched too.
+    ///
+    /// # Parameters
+    /// - `movie_name`: The name or keyword to search for.
+    /// - `filter`: A `Filter` struct specifying search filters (quality, genre, etc.).
+    /// - `post_filter`: Client-side criteria movies must meet.
+    /// - `limit`: Maximum number of movies to return.
+    ///
+    /// # Returns
+    /// A `Result` containing up to `limit` matching movies, or an error.
+    ///
+    /// # Errors
+    /// Returns an error if any HTTP request fails or a response cannot be parsed.
+    pub async fn search_until(
+        &self,
+        movie_name: &str,
+        mut filter: Filter,
+        post_filter: &PostFilter,
+        limit: usize,
+    ) -> crate::Result<Vec<Movie>> {
+        let mut movies = Vec::new();
+
+        while movies.len() < limit {
+            let mut response = self.search_with_filter(movie_name, filter.clone()).await?;
+            post_filter.apply(&mut response);
+
+            for movie in response.movies {
+                if movies.len() == limit {
+                    break;
+                }
+                if post_filter.needs_torrents()
+                    && !post_filter.matches_torrents(&self.torrents(&movie).await?)
+                {
+                    continue;
+                }
+                movies.push(movie);
+            }
+
+            match response.page.next_filter(&filter) {
+                Some(next) => filter = next,
+                None => break,
+            }
+        }
+
+        Ok(movies)
+    }
+
+    /// Checks that the parsers still understand the site, e.g. after a redesign.
+    ///
+    /// Searches a well-known title, then reads the torrents of the first movie found,
+    /// bypassing the cache. Failures are reported in the returned [`SelfTest`] rather than
+    /// as an error.
+    pub async fn self_test(&self) -> SelfTest {
+        let yts = self.bypass_cache();
+        let response = yts.search(SELF_TEST_QUERY).await;
+        let torrents = match response.as_ref().ok().and_then(|r| r.movies.first()) {
+            Some(movie) => Some(yts.torrents(movie).await.map(|torrents| torrents.len())),
+            None => None,
+        };
+
+        SelfTest {
+            browse: response.map(|response| response.movies.len()),
+            torrents,
+        }
+    }
+
+    /// Searches every page of the results, merging them into one deduplicated response.
+    ///
+    /// Starts at the page set in `filter`, then fetches the following pages up to the last
+    /// one or the page limit, several at a time. Movies already listed by an earlier page
+    /// are dropped, and pages that fail are reported in the statistics. An ascending
+    /// direction the site cannot serve applies to the merged movies, see
+    /// [`Direction`](crate::Direction).
+    ///
+    /// # Parameters
+    /// - `movie_name`: The name or keyword to search for.
+    /// - `filter`: A `Filter` struct specifying search filters (quality, genre, etc.).
+    /// - `limits`: Bounds on the number of pages and concurrent requests.
+    ///
+    /// # Returns
+    /// A `Result` containing the merged `Response` and crawl statistics, or an error.
+    ///
+    /// # Errors
+    /// Returns an error if the first page cannot be fetched or parsed.
+    pub async fn search_all(
+        &self,
+        movie_name: &str,
+        filter: Filter,
+        limits: CrawlLimits,
+    ) -> crate::Result<Crawl> {
+        let (filter, reverse) = filter.for_crawl();
+        let first = self.search_with_filter(movie_name, filter.clone()).await?;
+        let pages = limits.remaining(&first.page);
+        let last = (*pages.end()).max(first.page.current);
+
+        let mut others = Vec::new();
+        let mut tasks = tokio::task::JoinSet::new();
+        for page in pages {
+            if tasks.len() >= limits.concurrency {
+                others.extend(tasks.join_next().await.map(joined));
+            }
+
+            let yts = self.clone();
+            let movie_name = movie_name.to_string();
+            let filter = Filter {
+                page,
+                ..filter.clone()
+            };
+            tasks.spawn(async move { (page, yts.search_with_filter(&movie_name, filter).await) });
+        }
+        while let Some(result) = tasks.join_next().await {
+            others.push(joined(result));
+        }
+
+        let mut crawl = Crawl::merge(first, others, last);
+        if reverse {
+            crawl.response.movies.reverse();
+        }
+        Ok(crawl)
+    }
+
+    /// Finds the movie best matching a title and release year, e.g. from an import list.
+    ///
+    /// Titles are compared without case, accents, punctuation or articles, and with roman
+    /// numerals as numbers, so `Godfather Part 2` matches `The Godfather Part II`. Release
+    /// years may differ by one, at a lower confidence.
+    ///
+    /// # Parameters
+    /// - `title`: The title of the movie.
+    /// - `year`: The release year of the movie.
+    ///
+    /// # Returns
+    /// A `Result` containing the best `Match`, or `None` if no candidate is close enough.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub async fn find_best_match(&self, title: &str, year: u32) -> crate::Result<Option<Match>> {
+        let filter = crate::Filters::default().year(Match::years(year)).build();
+        let response = self.search_with_filter(title, filter).await?;
+
+        Ok(Match::best(title, year, response.movies))
+    }
+
+    /// Looks up a movie by its IMDb title id, e.g. `tt0068646`.
+    ///
+    /// The search results are checked in order, returning the first one whose movie page
+    /// links to the IMDb title. Pass the movie to [`torrents`](Self::torrents) for its
+    /// details.
+    ///
+    /// # Parameters
+    /// - `id`: The IMDb title id, in any case.
+    ///
+    /// # Returns
+    /// A `Result` containing the matching `Movie`, or an error.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidImdbId`](crate::Error::InvalidImdbId) if `id` is malformed,
+    /// [`Error::MovieNotFound`](crate::Error::MovieNotFound) if no result links to the IMDb
+    /// title, or an error if an HTTP request fails or a page cannot be parsed.
+    pub async fn by_imdb_id(&self, id: &str) -> crate::Result<Movie> {
+        let id = imdb::imdb_id(id)?;
+        let response = self.search(&id).await?;
+
+        for movie in response.movies {
+            let html = self.fetch(&movie.link, Endpoint::Movie).await?;
+            let linked = imdb::links_to(&html, &id, &self.selectors)
+                .inspect_err(|e| self.parse_failed(Endpoint::Movie, e))?;
+            if linked {
+                return Ok(movie);
+            }
+        }
+        Err(crate::Error::MovieNotFound(id))
+    }
+
+    /// Looks up several movies by their IMDb title ids, one after the other.
+    ///
+    /// # Parameters
+    /// - `ids`: The IMDb title ids.
+    ///
+    /// # Returns
+    /// The result of [`by_imdb_id`](Self::by_imdb_id) for each id, in the same order.
+    pub async fn by_imdb_ids<I>(&self, ids: I) -> Vec<crate::Result<Movie>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut movies = Vec::new();
+        for id in ids {
+            movies.push(self.by_imdb_id(id.as_ref()).await);
+        }
+        movies
+    }
+
     /// Retrieves torrent information for a given movie.
     ///
     /// # Parameters
@@ -107,16 +462,465 @@ impl<'a> Yts<'a> {
     /// # Errors
     /// Returns an error if the HTTP request fails or the response cannot be parsed.
     pub async fn torrents(&self, movie: &Movie) -> crate::Result<Vec<Torrent>> {
-        let client = reqwest::Client::new();
+        let html = self.fetch(&movie.link, Endpoint::Movie).await?;
+        Torrent::create(&html, &self.selectors)
+            .inspect_err(|e| self.parse_failed(Endpoint::Movie, e))
+    }
+
+    /// Picks the best torrent of a movie according to `policy`.
+    ///
+    /// # Parameters
+    /// - `movie`: Reference to a `Movie` struct.
+    /// - `policy`: Rules ranking and rejecting torrents.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Selection`, with the best torrent if any and the reasons
+    /// each other torrent was rejected.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails or the response cannot be parsed.
+    pub async fn best_torrent(
+        &self,
+        movie: &Movie,
+        policy: &TorrentPolicy,
+    ) -> crate::Result<Selection> {
+        let torrents = self.torrents(movie).await?;
+
+        Ok(Torrent::select_best(&torrents, policy))
+    }
+
+    /// Downloads the `.torrent` file of a torrent into `dir`.
+    ///
+    /// The file is named after the movie title, year and quality, made safe for the
+    /// filesystem, and never overwrites an existing file. Torrent files bypass the caches
+    /// and cassette.
+    ///
+    /// # Parameters
+    /// - `movie`: Reference to the `Movie` the torrent belongs to.
+    /// - `torrent`: Reference to a `Torrent` struct of the movie.
+    /// - `dir`: Directory to save the file in, created if missing.
+    ///
+    /// # Returns
+    /// A `Result` containing the path of the saved file and the torrent info hash.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails, the body is not a valid torrent file or
+    /// the file cannot be written.
+    pub async fn download_torrent(
+        &self,
+        movie: &Movie,
+        torrent: &Torrent,
+        dir: impl AsRef<Path>,
+    ) -> crate::Result<DownloadedTorrent> {
+        let url = &torrent.link;
+        let _permit = match &self.rate_limiter {
+            Some(rate_limiter) => Some(rate_limiter.acquire().await),
+            None => None,
+        };
+
+        let started = Instant::now();
+        let (status, bytes) = self
+            .request_file(url, self.timeout)
+            .await
+            .inspect_err(|e| {
+                self.observe(|observer| observer.on_request_error(Endpoint::Torrent, e))
+            })?;
+        self.observe(|observer| {
+            observer.on_response(
+                Endpoint::Torrent,
+                status.as_u16(),
+                started.elapsed(),
+                bytes.len(),
+            )
+        });
+
+        self.check_status(url, status)?;
+        download::save(&bytes, movie, torrent, dir.as_ref())
+    }
 
-        let response = client
-            .get(&movie.link)
+    /// Asks trackers for the current swarm of a torrent.
+    ///
+    /// The seeds and peers shown on the site are cached and often stale, while trackers
+    /// report the peers announcing now. UDP trackers are scraped as described by BEP 15,
+    /// and HTTP trackers at their scrape URL, several at a time. Trackers are not the site,
+    /// so the caches, cassette and rate limiter do not apply, and UDP trackers are reached
+    /// without proxies. Unanswered UDP requests are resent until the tracker times out.
+    ///
+    /// # Parameters
+    /// - `info_hash`: Info hash of the torrent, e.g. from `download_torrent`.
+    /// - `trackers`: Tracker announce URLs, e.g. from
+    ///   [`TorrentFile::trackers`](crate::TorrentFile::trackers).
+    /// - `limits`: Time allowed to each tracker and number of trackers asked at once.
+    ///
+    /// # Returns
+    /// A `Scrape` with the answer or error of each tracker.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(
+    /// #     yts: yts_movies::Yts,
+    /// #     movie: yts_movies::Movie,
+    /// #     torrent: yts_movies::Torrent,
+    /// # ) -> yts_movies::Result {
+    /// use yts_movies::{ScrapeLimits, TorrentFile};
+    ///
+    /// let downloaded = yts.download_torrent(&movie, &torrent, "torrents").await?;
+    /// let torrent_file = TorrentFile::parse(&std::fs::read(&downloaded.path)?)?;
+    /// let scrape = yts
+    ///     .scrape(downloaded.info_hash, &torrent_file.trackers(), ScrapeLimits::default())
+    ///     .await;
+    /// println!("{:?}", scrape.best());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scrape(
+        &self,
+        info_hash: InfoHash,
+        trackers: &[impl AsRef<str>],
+        limits: ScrapeLimits,
+    ) -> Scrape {
+        let mut results = Vec::new();
+        let mut tasks = tokio::task::JoinSet::new();
+        for (index, url) in trackers.iter().enumerate() {
+            if tasks.len() >= limits.concurrency {
+                results.extend(tasks.join_next().await.map(joined));
+            }
+
+            let yts = self.clone();
+            let url = url.as_ref().to_string();
+            tasks.spawn(async move {
+                let health = yts.scrape_tracker(&url, info_hash, limits.timeout).await;
+                (index, (url, health))
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            results.push(joined(result));
+        }
+
+        Scrape::collect(info_hash, results)
+    }
+
+    /// Asks the tracker at `url` for the swarm of a torrent, within `timeout`.
+    async fn scrape_tracker(
+        &self,
+        url: &str,
+        info_hash: InfoHash,
+        timeout: Duration,
+    ) -> crate::Result<SwarmHealth> {
+        let exchange = async {
+            match Tracker::parse(url)? {
+                Tracker::Udp(addr) => scrape_udp(url, &addr, &info_hash).await,
+                Tracker::Http(scrape_url) => {
+                    let scrape_url = scrape::http_url(&scrape_url, &info_hash);
+                    let (status, body) = self.request_file(&scrape_url, timeout).await?;
+                    self.check_status(&scrape_url, status)?;
+                    scrape::http_scraped(url, &body, &info_hash)
+                }
+            }
+        };
+
+        tokio::time::timeout(timeout, exchange)
+            .await
+            .map_err(|_| scrape::error(url, "timed out"))?
+    }
+
+    /// Fetches the body of a page, going through the cache when enabled.
+    ///
+    /// # Parameters
+    /// - `url`: URL of the page.
+    ///
+    /// # Returns
+    /// A `Result` containing the page body or an error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip(self),
+            fields(source, status, bytes, latency_ms),
+            err(level = "warn")
+        )
+    )]
+    async fn fetch(&self, url: &str, endpoint: Endpoint) -> crate::Result<String> {
+        if let Some(body) = self.cached(url) {
+            trace::record!(source = "memory", bytes = body.len());
+            self.observe(|observer| observer.on_cache_hit(endpoint, CacheKind::Memory));
+            return Ok(body);
+        }
+
+        let body = match self.load_stored(url)? {
+            Some(entry) if self.is_cache_only() => {
+                trace::record!(source = "disk", bytes = entry.body.len());
+                self.observe(|observer| observer.on_cache_hit(endpoint, CacheKind::Disk));
+                entry.body
+            }
+            stored => {
+                let fetched = match self.replayed(url)? {
+                    Some(fetched) => {
+                        trace::record!(source = "cassette");
+                        fetched
+                    }
+                    None => {
+                        let _permit = match &self.rate_limiter {
+                            Some(rate_limiter) => Some(rate_limiter.acquire().await),
+                            None => None,
+                        };
+
+                        let started = Instant::now();
+                        let fetched =
+                            self.request(url, stored.as_ref()).await.inspect_err(|e| {
+                                self.observe(|observer| observer.on_request_error(endpoint, e))
+                            })?;
+                        self.responded(endpoint, &fetched, started.elapsed());
+                        self.record(url, &fetched)?;
+                        fetched
+                    }
+                };
+                trace::record!(status = fetched.status.as_u16(), bytes = fetched.body.len());
+
+                self.accept(url, endpoint, stored, fetched)?
+            }
+        };
+
+        self.store(url, &body);
+
+        Ok(body)
+    }
+
+    /// Sends a request for `url`, revalidating the `stored` page if any.
+    async fn request(&self, url: &str, stored: Option<&DiskEntry>) -> crate::Result<Fetched> {
+        let response = self
+            .http_client()?
+            .get(url)
+            .headers(DiskEntry::conditional_headers(stored))
             .header(USER_AGENT, "Mozilla/5.0 (Linux x86_64)")
             .timeout(self.timeout)
             .send()
-            .await?;
+            .await
+            .map_err(|e| self.request_error(url, e))?;
 
-        Torrent::create(&response.text().await?)
+        Ok(Fetched {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.text().await?,
+        })
+    }
+
+    /// Sends a request for the binary file at `url`, within `timeout`.
+    async fn request_file(
+        &self,
+        url: &str,
+        timeout: Duration,
+    ) -> crate::Result<(StatusCode, Vec<u8>)> {
+        let response = self
+            .http_client()?
+            .get(url)
+            .header(USER_AGENT, "Mozilla/5.0 (Linux x86_64)")
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| self.request_error(url, e))?;
+
+        Ok((response.status(), response.bytes().await?.to_vec()))
+    }
+
+    /// Returns the shared HTTP client, building it on first use.
+    fn http_client(&self) -> crate::Result<&reqwest::Client> {
+        if let Some(client) = self.http.get() {
+            return Ok(client);
+        }
+
+        let mut client = reqwest::Client::builder();
+        if let Some(proxies) = self.routing() {
+            client = client.proxy(proxies.to_reqwest());
+        }
+        let client = client.build()?;
+
+        Ok(self.http.get_or_init(|| client))
+    }
+}
+
+impl Yts {
+    /// Reports a response received from the network.
+    pub(crate) fn responded(&self, endpoint: Endpoint, fetched: &Fetched, latency: Duration) {
+        trace::record!(source = "network", latency_ms = latency.as_millis() as u64);
+        self.observe(|observer| {
+            observer.on_response(
+                endpoint,
+                fetched.status.as_u16(),
+                latency,
+                fetched.body.len(),
+            )
+        });
+    }
+
+    /// Calls `event` on the observer, if any.
+    pub(crate) fn observe(&self, event: impl FnOnce(&dyn Observer)) {
+        if let Some(observer) = &self.observer {
+            event(observer.as_ref());
+        }
+    }
+
+    /// Reports a page that could not be parsed.
+    pub(crate) fn parse_failed(&self, endpoint: Endpoint, error: &crate::Error) {
+        self.observe(|observer| observer.on_parse_error(endpoint, error));
+    }
+
+    /// Converts a failed request into an error, blaming the proxy only for connection
+    /// failures of requests routed through one.
+    pub(crate) fn request_error(&self, url: &str, error: reqwest::Error) -> crate::Error {
+        let proxy = reqwest::Url::parse(url)
+            .ok()
+            .zip(self.routing())
+            .and_then(|(url, proxies)| proxies.proxy_for(&url).map(Proxy::redacted));
+
+        match proxy {
+            Some(proxy) if error.is_connect() => {
+                crate::Error::ProxyError(format!("{proxy}: {error}"))
+            }
+            _ => error.into(),
+        }
+    }
+
+    /// Returns the proxy routing of requests: the configured one, or else the one of the
+    /// environment variables, if they are valid.
+    pub(crate) fn routing(&self) -> Option<Cow<'_, Proxies>> {
+        match &self.proxies {
+            Some(proxies) => Some(Cow::Borrowed(proxies)),
+            None => Proxies::from_env().ok().map(Cow::Owned),
+        }
+    }
+
+    /// Returns the recorded page for `url` when replaying a cassette.
+    ///
+    /// # Errors
+    /// Returns an error if `url` is missing from the cassette.
+    pub(crate) fn replayed(&self, url: &str) -> crate::Result<Option<Fetched>> {
+        let Some(cassette) = self
+            .cassette
+            .as_ref()
+            .filter(|cassette| cassette.mode() == CassetteMode::Replay)
+        else {
+            return Ok(None);
+        };
+
+        let (status, body) = cassette.play(url)?;
+        let status = StatusCode::from_u16(status).map_err(|_| {
+            crate::Error::CassetteError(format!("invalid status {status} for {url}"))
+        })?;
+
+        Ok(Some(Fetched {
+            status,
+            headers: HeaderMap::new(),
+            body,
+        }))
+    }
+
+    /// Writes a page fetched from the network to the cassette, when recording.
+    ///
+    /// # Errors
+    /// Returns an error if the cassette cannot be written.
+    pub(crate) fn record(&self, url: &str, fetched: &Fetched) -> crate::Result {
+        match &self.cassette {
+            Some(cassette) if cassette.mode() == CassetteMode::Record => {
+                cassette.save(url, fetched.status.as_u16(), &fetched.body)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolves a fetched page to its body.
+    ///
+    /// A `304 Not Modified` reuses the body stored on disk, other non-success statuses
+    /// are rejected, and a fresh body is saved to the disk cache.
+    ///
+    /// # Errors
+    /// Returns an error for a non-success status or if the disk cache cannot be written.
+    pub(crate) fn accept(
+        &self,
+        url: &str,
+        endpoint: Endpoint,
+        stored: Option<DiskEntry>,
+        fetched: Fetched,
+    ) -> crate::Result<String> {
+        if let Some(entry) = stored
+            && fetched.status == StatusCode::NOT_MODIFIED
+        {
+            self.observe(|observer| observer.on_cache_hit(endpoint, CacheKind::Disk));
+            return Ok(entry.body);
+        }
+
+        self.check_status(url, fetched.status)?;
+        self.save_stored(url, &fetched.body, &fetched.headers)?;
+        Ok(fetched.body)
+    }
+
+    /// Rejects a non-success `status`, telling proxy authentication failures apart.
+    ///
+    /// # Errors
+    /// Returns an error for any status other than `2xx`.
+    pub(crate) fn check_status(&self, url: &str, status: StatusCode) -> crate::Result {
+        if status == StatusCode::PROXY_AUTHENTICATION_REQUIRED {
+            return Err(crate::Error::ProxyError(format!(
+                "proxy authentication required for {url}"
+            )));
+        }
+
+        if !status.is_success() {
+            return Err(crate::Error::StatusError(status.as_u16(), url.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if pages are served from the disk cache only.
+    pub(crate) fn is_cache_only(&self) -> bool {
+        self.disk_cache
+            .as_ref()
+            .is_some_and(DiskCache::is_cache_only)
+    }
+
+    /// Loads the disk cache entry for `url`, if the disk cache is enabled.
+    ///
+    /// # Errors
+    /// Returns an error if the entry cannot be read, or is missing in cache-only mode.
+    pub(crate) fn load_stored(&self, url: &str) -> crate::Result<Option<DiskEntry>> {
+        let Some(disk_cache) = &self.disk_cache else {
+            return Ok(None);
+        };
+
+        let stored = disk_cache.load(url)?;
+        if stored.is_none() && disk_cache.is_cache_only() {
+            return Err(crate::Error::NotCachedError(url.to_string()));
+        }
+
+        Ok(stored)
+    }
+
+    /// Saves a fetched page to the disk cache, if enabled.
+    ///
+    /// # Errors
+    /// Returns an error if the page cannot be written.
+    pub(crate) fn save_stored(&self, url: &str, body: &str, headers: &HeaderMap) -> crate::Result {
+        match &self.disk_cache {
+            Some(disk_cache) => disk_cache.save(url, &DiskEntry::new(body.to_string(), headers)?),
+            None => Ok(()),
+        }
+    }
+
+    /// Looks up `url` in the cache, unless caching is disabled or bypassed.
+    pub(crate) fn cached(&self, url: &str) -> Option<String> {
+        self.cache
+            .as_ref()
+            .filter(|_| !self.bypass_cache)
+            .and_then(|cache| cache.get(url))
+    }
+
+    /// Stores a fetched body in the cache, if caching is enabled.
+    pub(crate) fn store(&self, url: &str, body: &str) {
+        if let Some(cache) = &self.cache {
+            cache.insert(url, body);
+        }
     }
 
     /// Constructs the URL for a movie search with the specified filters.
@@ -127,40 +931,630 @@ impl<'a> Yts<'a> {
     ///
     /// # Returns
     /// A `String` containing the fully constructed URL.
+    ///
+    /// # Errors
+    /// Returns an error if the host is not a valid URL or the year filter is invalid.
     pub(crate) fn create_url(&self, movie_name: &str, filter: &Filter) -> crate::Result<String> {
-        let url: reqwest::Url = Url::parse(&format!(
-            "{}/browse-movies/{}",
-            self.host,
-            movie_name.trim()
-        ))
-        .map_err(|_| crate::Error::ParseError(self.host.to_string()))?;
-
-        Ok(format!(
-            "{}/{}/{}/{}/{}/{}/all{}",
-            url.as_str(),
-            filter.quality_to_str(),
-            filter.genre_to_str(),
-            filter.rating_to_str(),
-            filter.order_by_to_str(),
-            filter.year_to_str(),
-            if filter.page <= 1 {
-                ""
-            } else {
-                &format!("?page={}", filter.page)
+        filter.browse_url(&self.host, movie_name)
+    }
+}
+
+/// Asks the UDP tracker at `addr` for the swarm of a torrent, as described by BEP 15.
+///
+/// Requests are resent until answered; the caller bounds the whole scrape in time.
+#[cfg(feature = "async")]
+async fn scrape_udp(url: &str, addr: &str, info_hash: &InfoHash) -> crate::Result<SwarmHealth> {
+    let target = tokio::net::lookup_host(addr)
+        .await?
+        .next()
+        .ok_or_else(|| scrape::error(url, "no address"))?;
+    let socket = tokio::net::UdpSocket::bind(scrape::local_addr(&target)).await?;
+    socket.connect(target).await?;
+
+    let transaction_id = scrape::transaction_id();
+    let connection_id = udp_exchange(&socket, &scrape::connect_request(transaction_id), |r| {
+        scrape::connected(url, r, transaction_id)
+    })
+    .await?;
+
+    let transaction_id = scrape::transaction_id();
+    let request = scrape::scrape_request(connection_id, transaction_id, info_hash);
+    udp_exchange(&socket, &request, |r| {
+        scrape::scraped(url, r, transaction_id)
+    })
+    .await
+}
+
+/// Sends `request` on `socket` until `read` accepts a response, resending it every
+/// [`scrape::RETRANSMIT`]. Responses `read` maps to `None` are ignored.
+#[cfg(feature = "async")]
+async fn udp_exchange<T>(
+    socket: &tokio::net::UdpSocket,
+    request: &[u8],
+    read: impl Fn(&[u8]) -> crate::Result<Option<T>>,
+) -> crate::Result<T> {
+    let mut buffer = [0; scrape::MAX_PACKET];
+    loop {
+        socket.send(request).await?;
+        let resend = tokio::time::Instant::now() + scrape::RETRANSMIT;
+        while let Ok(length) = tokio::time::timeout_at(resend, socket.recv(&mut buffer)).await {
+            if let Some(value) = read(&buffer[..length?])? {
+                return Ok(value);
             }
-        ))
+        }
     }
 }
 
-#[cfg(test)]
+/// Returns the output of a finished task, resuming its panic if it panicked.
+#[cfg(feature = "async")]
+fn joined<T>(result: Result<T, tokio::task::JoinError>) -> T {
+    result.unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()))
+}
+
+#[cfg(all(test, feature = "async"))]
 mod test {
-    use crate::Filters;
+    use std::{sync::Arc, time::Duration};
+
+    use crate::{
+        Cache, CacheKind, CacheStats, Cassette, CrawlLimits, DiskCache, Endpoint, Filters,
+        Observer, OrderBy, Response, ScrapeLimits, TorrentPolicy, client::cassette,
+    };
 
     use super::Yts;
 
+    #[test]
+    fn test_create_url() {
+        let yts = Yts::default();
+
+        let url = yts
+            .create_url("the godfather", &Filters::default().build())
+            .unwrap();
+        assert_eq!(
+            url,
+            "https://yts.lt/browse-movies/the%20godfather/all/all/0/latest/0/all"
+        );
+
+        let url = yts
+            .create_url(
+                "the godfather",
+                &Filters::default()
+                    .year(crate::Year::Range(1971, 1975))
+                    .page(2)
+                    .build(),
+            )
+            .unwrap();
+        assert_eq!(
+            url,
+            "https://yts.lt/browse-movies/the%20godfather/all/all/0/latest/1970-1979/all?page=2"
+        );
+
+        assert!(
+            yts.create_url(
+                "the godfather",
+                &Filters::default()
+                    .year(crate::Year::Range(1980, 1970))
+                    .build(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_owned_client() {
+        fn assert_shareable<T: Send + Sync + Clone + 'static>() {}
+        assert_shareable::<Yts>();
+        fn assert_thread_safe<T: Send + Sync + 'static>() {}
+        assert_thread_safe::<crate::Error>();
+
+        let host = String::from("https://yts.mx/");
+        let yts = Yts::new(host, Duration::from_secs(10));
+        assert_eq!(yts.host(), "https://yts.mx");
+
+        let url = reqwest::Url::parse("https://yts.mx").unwrap();
+        let yts = Arc::new(Yts::new(url, Duration::from_secs(10)));
+        assert_eq!(
+            yts.create_url("up", &Filters::default().build()).unwrap(),
+            "https://yts.mx/browse-movies/up/all/all/0/latest/0/all"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_search_from_cache() {
+        let yts = Yts::default().with_cache(Cache::new(Duration::from_secs(60), 10));
+        let url = yts
+            .create_url("the godfather", &Filters::default().build())
+            .unwrap();
+        yts.store(
+            &url,
+            include_str!("../../tests/fixtures/pages/browse-empty.html"),
+        );
+
+        let results = yts.clone().search("the godfather").await;
+
+        assert!(results.is_ok());
+        assert_eq!(
+            yts.cache_stats(),
+            Some(CacheStats {
+                hits: 1,
+                misses: 0,
+                entries: 1
+            })
+        );
+        assert!(yts.bypass_cache().cached(&url).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_async_search_cache_only() {
+        let dir =
+            std::env::temp_dir().join(format!("yts-movies-cache-only-{}", std::process::id()));
+        let yts = Yts::default().with_disk_cache(DiskCache::new(&dir).cache_only());
+        let url = yts
+            .create_url("the godfather", &Filters::default().build())
+            .unwrap();
+
+        assert!(matches!(
+            yts.search("the godfather").await,
+            Err(crate::Error::NotCachedError(_))
+        ));
+
+        yts.save_stored(
+            &url,
+            include_str!("../../tests/fixtures/pages/browse-empty.html"),
+            &Default::default(),
+        )
+        .unwrap();
+        let results = yts.search("the godfather").await;
+
+        assert!(results.is_ok());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_async_search_status_error() {
+        let dir = std::env::temp_dir().join(format!("yts-movies-status-{}", std::process::id()));
+        let url = Yts::default()
+            .create_url("the godfather", &Filters::default().build())
+            .unwrap();
+        Cassette::record(&dir)
+            .unwrap()
+            .save(&url, 503, "Service Unavailable")
+            .unwrap();
+
+        let yts = Yts::default().with_cassette(Cassette::replay(&dir).unwrap());
+
+        assert!(matches!(
+            yts.search("the godfather").await,
+            Err(crate::Error::StatusError(503, _))
+        ));
+        assert!(matches!(
+            yts.search("the godfather part ii").await,
+            Err(crate::Error::CassetteError(_))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_async_rate_limiter_shared_across_clones() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        server.browse("godfather", &Filters::default().build(), 0, &[]);
+
+        let yts = Yts::new(server.host(), Duration::from_secs(5))
+            .with_rate_limiter(crate::RateLimiter::new(10.0, 1));
+        let start = std::time::Instant::now();
+
+        yts.clone().search("godfather").await.unwrap();
+        yts.clone().search("godfather").await.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(90));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_async_search_through_proxy() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        server.browse("godfather", &Filters::default().build(), 0, &[]);
+        let proxy = crate::Proxy::new(server.host()).unwrap();
+
+        let yts = Yts::new("http://yts.invalid", Duration::from_secs(5))
+            .with_proxies(crate::Proxies::new().host("yts.invalid", proxy));
+        assert!(yts.search("godfather").await.is_ok());
+
+        let closed = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let proxy = crate::Proxy::new(&format!("socks5://{closed}")).unwrap();
+        let yts = yts.with_proxies(crate::Proxies::new().all(proxy.clone()));
+        assert!(matches!(
+            yts.search("godfather").await,
+            Err(crate::Error::ProxyError(_))
+        ));
+
+        let yts = Yts::new(format!("http://{closed}"), Duration::from_secs(5))
+            .with_proxies(crate::Proxies::new().all(proxy).no_proxy("127.0.0.1"));
+        assert!(matches!(
+            yts.search("godfather").await,
+            Err(crate::Error::ReqwestError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_async_observer() {
+        #[derive(Default)]
+        struct Events(std::sync::Mutex<Vec<String>>);
+
+        impl Observer for Arc<Events> {
+            fn on_response(&self, endpoint: Endpoint, status: u16, _: Duration, bytes: usize) {
+                let event = format!("{} {status} {}", endpoint.name(), bytes > 0);
+                self.0.lock().unwrap().push(event);
+            }
+
+            fn on_cache_hit(&self, endpoint: Endpoint, cache: CacheKind) {
+                let event = format!("{} {}", endpoint.name(), cache.name());
+                self.0.lock().unwrap().push(event);
+            }
+
+            fn on_parse_error(&self, endpoint: Endpoint, error: &crate::Error) {
+                let event = format!("{} {}", endpoint.name(), error.name());
+                self.0.lock().unwrap().push(event);
+            }
+        }
+
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let godfather = crate::test_util::movie("The Godfather", 1972, 9.2, vec![]);
+        server.browse("godfather", &Filters::default().build(), 1, &[&godfather]);
+        server.page("/movies/the-godfather-1972", 200, "<html></html>");
+        server.status("/browse-movies/missing/all/all/0/latest/0/all", 503);
+
+        let events = Arc::new(Events::default());
+        let yts = Yts::new(server.host(), Duration::from_secs(5))
+            .with_cache(Cache::new(Duration::from_secs(60), 10))
+            .with_observer(Arc::clone(&events));
+
+        let response = yts.search("godfather").await.unwrap();
+        yts.search("godfather").await.unwrap();
+        assert!(yts.torrents(&response.movies[0]).await.is_err());
+        assert!(yts.search("missing").await.is_err());
+
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            [
+                "browse 200 true",
+                "browse memory",
+                "movie 200 true",
+                "movie LayoutChanged",
+                "browse 503 false"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_search_all() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let movies = ["Alien", "Aliens", "Alien 3", "Prometheus"]
+            .map(|name| crate::test_util::movie(name, 1979, 8.5, vec![]));
+        let filter = Filters::default().build();
+        server.browse("alien", &filter, 50, &[&movies[0], &movies[1]]);
+        server.browse(
+            "alien",
+            &Filters::default().page(2).build(),
+            50,
+            &[&movies[1], &movies[2]],
+        );
+        server.status("/browse-movies/alien/all/all/0/latest/0/all?page=3", 503);
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+
+        let crawl = yts
+            .search_all("alien", filter.clone(), CrawlLimits::default())
+            .await
+            .unwrap();
+
+        let names = crawl
+            .response
+            .movies
+            .iter()
+            .map(|movie| movie.name.as_str());
+        assert_eq!(names.collect::<Vec<_>>(), ["Alien", "Aliens", "Alien 3"]);
+        assert_eq!((crawl.stats.pages, crawl.stats.duplicates), (2, 1));
+        assert!(matches!(
+            crawl.stats.failures[..],
+            [(3, crate::Error::StatusError(503, _))]
+        ));
+        assert!(!crawl.response.page.has_next());
+
+        let crawl = yts
+            .search_all("alien", filter, CrawlLimits::default().max_pages(2))
+            .await
+            .unwrap();
+
+        assert_eq!(crawl.stats.pages, 2);
+        assert!(crawl.stats.failures.is_empty());
+        assert_eq!(crawl.response.page.next, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_async_search_ascending_seeds() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let movies = ["Alien", "Aliens", "Alien 3", "Prometheus"]
+            .map(|name| crate::test_util::movie(name, 1979, 8.5, vec![]));
+        let descending = Filters::default().order_by(OrderBy::Seeds);
+        server.browse("alien", &descending.build(), 40, &[&movies[0], &movies[1]]);
+        server.browse(
+            "alien",
+            &Filters::default().order_by(OrderBy::Seeds).page(2).build(),
+            40,
+            &[&movies[2], &movies[3]],
+        );
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+        let ascending = Filters::default()
+            .order_by(OrderBy::Seeds)
+            .direction(crate::Direction::Ascending)
+            .build();
+        let names = |response: &Response| {
+            response
+                .movies
+                .iter()
+                .map(|movie| movie.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let page = yts
+            .search_with_filter("alien", ascending.clone())
+            .await
+            .unwrap();
+        assert_eq!(names(&page), ["Aliens", "Alien"]);
+
+        let crawl = yts
+            .search_all("alien", ascending, CrawlLimits::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            names(&crawl.response),
+            ["Prometheus", "Alien 3", "Aliens", "Alien"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_find_best_match() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let first = crate::test_util::movie("The Godfather", 1972, 9.2, vec![]);
+        let second = crate::test_util::movie("The Godfather Part II", 1974, 9.0, vec![]);
+        let third = crate::test_util::movie("The Godfather Part III", 1990, 7.6, vec![]);
+        server.browse(
+            "Godfather Part 2",
+            &Filters::default()
+                .year(crate::Year::Range(1973, 1975))
+                .build(),
+            3,
+            &[&third, &second, &first],
+        );
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+
+        let found = yts
+            .find_best_match("Godfather Part 2", 1974)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(found.movie.name, "The Godfather Part II");
+        assert_eq!(found.confidence, 1.0);
+        assert!(
+            server.requests()[0]
+                .starts_with("/browse-movies/Godfather%20Part%202/all/all/0/latest/1970-1979/")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_find_best_match_current_year() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let year = crate::Year::current();
+        let movie = crate::test_util::movie("Sinners", year, 7.8, vec![]);
+        server.browse(
+            "Sinners",
+            &Filters::default().year(crate::Match::years(year)).build(),
+            1,
+            &[&movie],
+        );
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+
+        let found = yts.find_best_match("Sinners", year).await.unwrap().unwrap();
+
+        assert_eq!(found.movie.name, "Sinners");
+        assert_eq!(found.movie.year, year);
+    }
+
+    #[tokio::test]
+    async fn test_async_by_imdb_id() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let godfather = crate::test_util::movie("The Godfather", 1972, 9.2, vec![]);
+        let sequel = crate::test_util::movie("The Godfather Part II", 1974, 9.0, vec![]);
+        server.browse(
+            "tt0068646",
+            &Filters::default().build(),
+            2,
+            &[&sequel, &godfather],
+        );
+        server.browse("tt0000001", &Filters::default().build(), 0, &[]);
+        server.browse("tt0099674", &Filters::default().build(), 1, &[&sequel]);
+        server.imdb_movie(&godfather, "tt0068646", &[]);
+        server.imdb_movie(&sequel, "tt0071562", &[]);
+        let yts = Yts::new(server.host(), Duration::from_secs(5))
+            .with_cache(Cache::new(Duration::from_secs(60), 10));
+
+        let movie = yts.by_imdb_id("TT0068646").await.unwrap();
+        assert_eq!(movie.name, "The Godfather");
+
+        let movies = yts
+            .by_imdb_ids(["tt0068646", "tt0000001", "0068646", "tt0099674"])
+            .await;
+        assert_eq!(movies[0].as_ref().unwrap().year, 1972);
+        assert!(matches!(
+            &movies[1],
+            Err(crate::Error::MovieNotFound(id)) if id == "tt0000001"
+        ));
+        assert!(matches!(&movies[2], Err(crate::Error::InvalidImdbId(_))));
+        assert!(matches!(
+            &movies[3],
+            Err(crate::Error::MovieNotFound(id)) if id == "tt0099674"
+        ));
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_async_best_torrent() {
+        let yts = Yts::default().with_cassette(cassette::fixture("godfather"));
+        let response = yts.search("godfather").await.unwrap();
+        let movie = &response.movies[0];
+
+        let selection = yts
+            .best_torrent(movie, &TorrentPolicy::default())
+            .await
+            .unwrap();
+        assert!(selection.best.is_some());
+        assert!(selection.rejected.is_empty());
+
+        let policy = TorrentPolicy::default().exclude(crate::Resolution::P720);
+        let selection = yts.best_torrent(movie, &policy).await.unwrap();
+        assert!(
+            selection
+                .rejected
+                .iter()
+                .all(|rejected| rejected.torrent.quality.resolution == crate::Resolution::P720)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_download_torrent() {
+        let server = crate::test_util::FakeYts::start().unwrap();
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+        let dir = std::env::temp_dir().join(format!("yts-movies-download-{}", std::process::id()));
+        let movie = crate::test_util::movie("The Godfather", 1972, 9.2, vec![]);
+        let mut torrent = crate::test_util::torrent(
+            crate::Quality::new(crate::Resolution::P720),
+            "1.15 GB",
+            "2 hr 55 min",
+            1,
+            2,
+        );
+        torrent.link = format!("{}/torrent/download/godfather", server.host());
+        server.page(
+            "/torrent/download/godfather",
+            200,
+            "d4:infod6:lengthi1234e4:name27:The Godfather (1972) [720p]\
+             12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaaee",
+        );
+
+        let first = yts.download_torrent(&movie, &torrent, &dir).await.unwrap();
+        let second = yts.download_torrent(&movie, &torrent, &dir).await.unwrap();
+        assert_eq!(first.path, dir.join("The Godfather (1972) [720p].torrent"));
+        assert_eq!(
+            second.path,
+            dir.join("The Godfather (1972) [720p] (1).torrent")
+        );
+        assert_eq!(
+            first.info_hash.to_string(),
+            "6d854e5ba938ce911e2444f77ce259c0affe2f11"
+        );
+
+        server.page("/torrent/download/godfather", 200, "<html></html>");
+        let result = yts.download_torrent(&movie, &torrent, &dir).await;
+        assert!(matches!(result, Err(crate::Error::InvalidTorrent(_))));
+
+        server.status("/torrent/download/godfather", 404);
+        let result = yts.download_torrent(&movie, &torrent, &dir).await;
+        assert!(matches!(result, Err(crate::Error::StatusError(404, _))));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_async_scrape() {
+        let tracker = crate::test_util::FakeTracker::start().unwrap();
+        let silent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let info_hash = crate::InfoHash::from([7; 20]);
+        let health = crate::SwarmHealth {
+            seeders: 120,
+            leechers: 8,
+            completed: 4500,
+        };
+        tracker.swarm(info_hash, health);
+        let trackers = [
+            tracker.udp_url(),
+            format!("udp://{}/announce", silent.local_addr().unwrap()),
+            tracker.http_url(),
+            "wss://tracker.local/announce".to_string(),
+        ];
+        let limits = ScrapeLimits::default()
+            .timeout(Duration::from_millis(300))
+            .concurrency(2);
+
+        let scrape = Yts::default().scrape(info_hash, &trackers, limits).await;
+
+        assert_eq!(scrape.trackers.len(), 4);
+        assert_eq!(scrape.trackers[0].0, trackers[0]);
+        assert_eq!(scrape.trackers[0].1.as_ref().unwrap(), &health);
+        assert!(matches!(
+            &scrape.trackers[1].1,
+            Err(crate::Error::TrackerError(e)) if e.ends_with("timed out")
+        ));
+        assert_eq!(scrape.trackers[2].1.as_ref().unwrap(), &health);
+        assert!(matches!(
+            scrape.trackers[3].1,
+            Err(crate::Error::TrackerError(_))
+        ));
+        assert_eq!(scrape.best(), Some(health));
+
+        let unknown = crate::InfoHash::from([8; 20]);
+        let scrape = Yts::default().scrape(unknown, &trackers[..1], limits).await;
+        assert!(matches!(
+            &scrape.trackers[0].1,
+            Err(crate::Error::TrackerError(e)) if e.ends_with("unknown torrent")
+        ));
+        assert_eq!(scrape.best(), None);
+
+        tracker.lose_udp(1);
+        tracker.stray_udp(true);
+        let limits = limits.timeout(Duration::from_secs(2));
+        let scrape = Yts::default()
+            .scrape(info_hash, &trackers[..1], limits)
+            .await;
+        assert_eq!(scrape.trackers[0].1.as_ref().unwrap(), &health);
+    }
+
+    #[tokio::test]
+    async fn test_async_self_test() {
+        let report = Yts::default()
+            .with_cassette(cassette::fixture("godfather"))
+            .self_test()
+            .await;
+
+        assert!(report.is_ok());
+        assert!(matches!(report.torrents, Some(Ok(torrents)) if torrents > 0));
+
+        let server = crate::test_util::FakeYts::start().unwrap();
+        server.page(
+            &server
+                .browse_target("godfather", &Filters::default().build())
+                .unwrap(),
+            200,
+            include_str!("../../tests/fixtures/pages/browse-thousands.html")
+                .replace("browse-movie-wrap", "movie-card"),
+        );
+
+        let report = Yts::new(server.host(), Duration::from_secs(5))
+            .self_test()
+            .await;
+
+        assert_eq!(report.broken(), [Endpoint::Browse, Endpoint::Movie]);
+        assert!(report.torrents.is_none());
+        assert!(matches!(
+            report.browse,
+            Err(crate::Error::LayoutChanged(_, missing)) if missing == "movie_card"
+        ));
+    }
+
     #[tokio::test]
     async fn test_async_search_with_filters() {
-        let yts = Yts::default();
+        let yts = Yts::default().with_cassette(cassette::fixture("godfather-1972"));
         let results = yts
             .search_with_filter(
                 "the godfather",
@@ -173,11 +1567,12 @@ mod test {
         let torrents = yts.torrents(&results.unwrap().movies[1]).await;
 
         assert!(torrents.is_ok());
+        assert_eq!(torrents.unwrap().len(), 3);
     }
 
     #[tokio::test]
     async fn test_async_search() {
-        let yts = Yts::default();
+        let yts = Yts::default().with_cassette(cassette::fixture("killers-of-the-flower-moon"));
         let results = yts.search("killers of the flower moon").await;
 
         assert!(results.is_ok());
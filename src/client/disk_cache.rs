@@ -0,0 +1,252 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use reqwest::header::{
+    ETAG, HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+
+/// Persistent cache of page bodies stored in a directory.
+///
+/// Each page is kept with its `ETag` and `Last-Modified` headers. Cached pages are
+/// revalidated with `If-None-Match` / `If-Modified-Since`, so an unchanged page costs a
+/// `304 Not Modified` instead of a full download. In cache-only mode no request is sent
+/// at all, and pages missing from the directory are reported as
+/// [`Error::NotCachedError`](crate::Error::NotCachedError).
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::{DiskCache, Yts};
+///
+/// let yts = Yts::default().with_disk_cache(DiskCache::new("/tmp/yts-cache"));
+/// let offline = Yts::default().with_disk_cache(DiskCache::new("/tmp/yts-cache").cache_only());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    /// Directory holding the cached pages.
+    dir: PathBuf,
+    /// Whether pages are served from disk only, without network access.
+    cache_only: bool,
+}
+
+/// A page stored in the [`DiskCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiskEntry {
+    /// The page body.
+    pub(crate) body: String,
+    /// Value of the `ETag` header, if sent.
+    pub(crate) etag: Option<String>,
+    /// Value of the `Last-Modified` header, if sent.
+    pub(crate) last_modified: Option<String>,
+}
+
+impl DiskCache {
+    /// Creates a disk cache in `dir`. The directory is created on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            cache_only: false,
+        }
+    }
+
+    /// Serves pages from disk only, never touching the network.
+    pub fn cache_only(mut self) -> Self {
+        self.cache_only = true;
+        self
+    }
+
+    /// Returns `true` if pages are served from disk only.
+    pub fn is_cache_only(&self) -> bool {
+        self.cache_only
+    }
+
+    /// Returns the directory holding the cached pages.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Loads the stored page for `url`, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the cache files exist but cannot be read.
+    pub(crate) fn load(&self, url: &str) -> crate::Result<Option<DiskEntry>> {
+        let (body_path, meta_path) = self.paths(url);
+
+        let meta = match fs::read_to_string(meta_path) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut stored_url = None;
+        let mut etag = None;
+        let mut last_modified = None;
+        for line in meta.lines() {
+            match line.split_once(": ") {
+                Some(("url", value)) => stored_url = Some(value),
+                Some(("etag", value)) => etag = Some(value.to_string()),
+                Some(("last-modified", value)) => last_modified = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        // Different URLs may share a file name; only the URL written alongside counts.
+        if stored_url != Some(url) {
+            return Ok(None);
+        }
+
+        let body = match fs::read_to_string(body_path) {
+            Ok(body) => body,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Some(DiskEntry {
+            body,
+            etag,
+            last_modified,
+        }))
+    }
+
+    /// Stores the page for `url`, replacing any previous version.
+    ///
+    /// # Errors
+    /// Returns an error if the directory or files cannot be written.
+    pub(crate) fn save(&self, url: &str, entry: &DiskEntry) -> crate::Result {
+        fs::create_dir_all(&self.dir)?;
+        let (body_path, meta_path) = self.paths(url);
+
+        let mut meta = format!("url: {url}\n");
+        if let Some(etag) = &entry.etag {
+            meta.push_str(&format!("etag: {etag}\n"));
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            meta.push_str(&format!("last-modified: {last_modified}\n"));
+        }
+
+        fs::write(body_path, &entry.body)?;
+        fs::write(meta_path, meta)?;
+
+        Ok(())
+    }
+
+    /// Returns the body and metadata file paths for `url`.
+    fn paths(&self, url: &str) -> (PathBuf, PathBuf) {
+        let name = format!("{:016x}", fnv1a(url.as_bytes()));
+        (
+            self.dir.join(format!("{name}.html")),
+            self.dir.join(format!("{name}.meta")),
+        )
+    }
+}
+
+impl DiskEntry {
+    /// Builds an entry from a fetched body and the response headers.
+    ///
+    /// # Errors
+    /// Returns an error if a validator header is not valid text.
+    pub(crate) fn new(body: String, headers: &HeaderMap) -> crate::Result<Self> {
+        let header = |name| -> crate::Result<Option<String>> {
+            Ok(headers
+                .get(name)
+                .map(HeaderValue::to_str)
+                .transpose()?
+                .map(str::to_string))
+        };
+
+        Ok(Self {
+            etag: header(ETAG)?,
+            last_modified: header(LAST_MODIFIED)?,
+            body,
+        })
+    }
+
+    /// Returns the conditional request headers revalidating this entry.
+    pub(crate) fn conditional_headers(entry: Option<&DiskEntry>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let Some(entry) = entry else {
+            return headers;
+        };
+
+        if let Some(value) = entry
+            .etag
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(IF_NONE_MATCH, value);
+        }
+        if let Some(value) = entry
+            .last_modified
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(IF_MODIFIED_SINCE, value);
+        }
+
+        headers
+    }
+}
+
+/// 64-bit FNV-1a hash, used for file names that must stay stable across releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::header::{ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+    use super::{DiskCache, DiskEntry};
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "yts-movies-{name}-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_disk_cache_round_trip() {
+        let cache = DiskCache::new(temp_dir("round-trip"));
+        let entry = DiskEntry {
+            body: "<html></html>".to_string(),
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+        };
+
+        assert_eq!(cache.load("https://yts.lt/a").unwrap(), None);
+        cache.save("https://yts.lt/a", &entry).unwrap();
+        assert_eq!(cache.load("https://yts.lt/a").unwrap(), Some(entry));
+        assert_eq!(cache.load("https://yts.lt/b").unwrap(), None);
+
+        std::fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_disk_entry_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, "\"abc\"".parse().unwrap());
+        headers.insert(
+            LAST_MODIFIED,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+
+        let entry = DiskEntry::new("body".to_string(), &headers).unwrap();
+        let conditional = DiskEntry::conditional_headers(Some(&entry));
+
+        assert_eq!(conditional[IF_NONE_MATCH], "\"abc\"");
+        assert_eq!(
+            conditional[IF_MODIFIED_SINCE],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+        assert!(DiskEntry::conditional_headers(None).is_empty());
+    }
+}
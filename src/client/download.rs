@@ -0,0 +1,178 @@
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{InfoHash, Movie, Torrent, TorrentFile};
+
+/// Longest file stem written, in bytes, leaving room for a suffix and the extension
+/// within the usual 255 byte limit.
+const MAX_STEM: usize = 200;
+
+/// Names reserved by Windows, whatever the extension.
+const RESERVED: [&str; 22] = [
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// A `.torrent` file saved by `download_torrent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedTorrent {
+    /// Path of the saved file.
+    pub path: PathBuf,
+    /// Info hash of the torrent, truncated from the v2 info hash for a v2-only torrent.
+    pub info_hash: InfoHash,
+}
+
+/// Validates the `.torrent` file in `bytes` and saves it in `dir` without overwriting.
+///
+/// The file is named after the movie title and year and the torrent quality, e.g.
+/// `The Godfather (1972) [720p].torrent`, never after the name inside the torrent. When
+/// a file of that name exists, a counter is added, e.g. `... (1).torrent`.
+///
+/// # Errors
+/// Returns [`Error::InvalidTorrent`](crate::Error::InvalidTorrent) if `bytes` is not a
+/// valid torrent file, or an error if the file cannot be written.
+pub(crate) fn save(
+    bytes: &[u8],
+    movie: &Movie,
+    torrent: &Torrent,
+    dir: &Path,
+) -> crate::Result<DownloadedTorrent> {
+    let torrent_file = TorrentFile::parse(bytes)?;
+    let info_hash = match (torrent_file.info_hash, torrent_file.info_hash_v2) {
+        (Some(info_hash), _) => info_hash,
+        (None, Some(info_hash_v2)) => info_hash_v2.truncated(),
+        (None, None) => unreachable!("a parsed torrent is v1, v2 or both"),
+    };
+
+    let stem = sanitize(&format!(
+        "{} ({}) [{}]",
+        movie.name, movie.year, torrent.quality
+    ));
+
+    std::fs::create_dir_all(dir)?;
+    for attempt in 0.. {
+        let file_name = match attempt {
+            0 => format!("{stem}.torrent"),
+            _ => format!("{stem} ({attempt}).torrent"),
+        };
+        let path = dir.join(file_name);
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(bytes)?;
+                return Ok(DownloadedTorrent { path, info_hash });
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    unreachable!("file name attempts are unbounded")
+}
+
+/// Returns `name` as a file stem safe on common filesystems.
+///
+/// Runs of whitespace collapse, leading and trailing dots are dropped, and path
+/// separators, characters reserved on Windows and control characters become `_`.
+fn sanitize(name: &str) -> String {
+    let collapsed = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut stem = collapsed
+        .trim_matches(['.', ' '])
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>();
+
+    if stem.len() > MAX_STEM {
+        let mut end = MAX_STEM;
+        while !stem.is_char_boundary(end) {
+            end -= 1;
+        }
+        stem.truncate(end);
+        stem = stem.trim_end_matches(['.', ' ']).to_string();
+    }
+
+    let base = stem.split('.').next().unwrap_or_default().trim_end();
+    if RESERVED.contains(&base.to_ascii_lowercase().as_str()) {
+        stem.insert(0, '_');
+    }
+
+    stem
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Quality, Resolution, Torrent};
+
+    use super::{sanitize, save};
+
+    /// A minimal single-file torrent.
+    pub(crate) const TORRENT: &[u8] = b"d8:announce33:udp://tracker.local:1337/announce4:infod6:lengthi1234e4:name36:The Godfather (1972) [720p] [YTS.MX]12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaaee";
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(
+            sanitize("The Godfather (1972) [720p]"),
+            "The Godfather (1972) [720p]"
+        );
+        assert_eq!(
+            sanitize("Face/Off: Director's Cut?"),
+            "Face_Off_ Director's Cut_"
+        );
+        assert_eq!(sanitize("  ..\t Se7en\n  (1995)..  "), "Se7en (1995)");
+        assert_eq!(sanitize("../../etc/passwd"), "_.._etc_passwd");
+        assert_eq!(sanitize("CON"), "_CON");
+        assert_eq!(sanitize("nul.txt"), "_nul.txt");
+        assert_eq!(sanitize(&"é".repeat(150)).len(), 200);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir().join(format!("yts-movies-save-{}", std::process::id()));
+        let movie = crate::test_util::movie("The Godfather", 1972, 9.2, vec![]);
+        let torrent = Torrent::new(
+            Quality::new(Resolution::P720),
+            "1.15 GB",
+            "English",
+            "2 hr 55 min",
+            "1 / 2",
+            String::new(),
+        );
+
+        let first = save(TORRENT, &movie, &torrent, &dir).unwrap();
+        let second = save(TORRENT, &movie, &torrent, &dir).unwrap();
+
+        assert_eq!(first.path, dir.join("The Godfather (1972) [720p].torrent"));
+        assert_eq!(
+            second.path,
+            dir.join("The Godfather (1972) [720p] (1).torrent")
+        );
+        assert_eq!(
+            first.info_hash.to_string(),
+            "47f039f46b7d14fdd61cab1a0463b42722248946"
+        );
+        assert_eq!(std::fs::read(&first.path).unwrap(), TORRENT);
+
+        let renamed = b"d4:infod6:lengthi1234e4:name15:Another Movie 212:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaaee";
+        let movie = crate::test_util::movie("Face/Off", 1997, 7.3, vec![]);
+        assert_eq!(
+            save(renamed, &movie, &torrent, &dir).unwrap().path,
+            dir.join("Face_Off (1997) [720p].torrent")
+        );
+
+        assert!(matches!(
+            save(b"<html></html>", &movie, &torrent, &dir),
+            Err(crate::Error::InvalidTorrent(_))
+        ));
+        assert!(matches!(
+            save(b"d8:announce3:urle", &movie, &torrent, &dir),
+            Err(crate::Error::InvalidTorrent(_))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
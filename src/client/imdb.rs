@@ -0,0 +1,92 @@
+use scraper::Html;
+
+use crate::SelectorProfile;
+
+/// Returns `id` as a lowercase IMDb title id, e.g. `tt0068646` for ` TT0068646`.
+///
+/// # Errors
+/// Returns [`Error::InvalidImdbId`](crate::Error::InvalidImdbId) unless `id` is `tt`
+/// followed by 7 to 10 digits.
+pub(crate) fn imdb_id(id: &str) -> crate::Result<String> {
+    let normalized = id.trim().to_ascii_lowercase();
+
+    match normalized.strip_prefix("tt") {
+        Some(digits)
+            if (7..=10).contains(&digits.len()) && digits.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            Ok(normalized)
+        }
+        _ => Err(crate::Error::InvalidImdbId(id.to_string())),
+    }
+}
+
+/// Returns `true` if the movie page `html` links to the IMDb title `id`.
+///
+/// # Errors
+/// Returns [`Error::LayoutChanged`](crate::Error::LayoutChanged) if the page has no IMDb
+/// link at all.
+pub(crate) fn links_to(html: &str, id: &str, profile: &SelectorProfile) -> crate::Result<bool> {
+    let document = Html::parse_document(html);
+    let mut links = document
+        .select(&profile.compiled.imdb_link)
+        .filter_map(|link| link.attr("href"))
+        .peekable();
+
+    if links.peek().is_none() {
+        return Err(profile.layout_changed(&["imdb_link"]));
+    }
+    Ok(links.any(|href| title_id(href).is_some_and(|title| title == id)))
+}
+
+/// Returns the lowercase IMDb title id of a link such as
+/// `https://www.imdb.com/title/tt0068646/`.
+fn title_id(href: &str) -> Option<String> {
+    let (_, path) = href.split_once("/title/")?;
+    let title = path.split(['/', '?', '#']).next()?;
+    imdb_id(title).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::SelectorProfile;
+
+    use super::{imdb_id, links_to};
+
+    #[test]
+    fn test_imdb_id() {
+        assert_eq!(imdb_id("tt0068646").unwrap(), "tt0068646");
+        assert_eq!(imdb_id(" TT10872600 ").unwrap(), "tt10872600");
+
+        for invalid in ["0068646", "tt068646", "tt00686a6", "nm0000199", "tt"] {
+            assert!(matches!(
+                imdb_id(invalid),
+                Err(crate::Error::InvalidImdbId(id)) if id == invalid
+            ));
+        }
+    }
+
+    #[test]
+    fn test_links_to() {
+        let profile = SelectorProfile::default();
+        let page = |href: &str| {
+            format!(r#"<html><body><a href="{href}" title="IMDb Rating">8.9</a></body></html>"#)
+        };
+
+        let html = page("https://www.imdb.com/title/tt0068646/?ref_=yts");
+        assert!(links_to(&html, "tt0068646", &profile).unwrap());
+        assert!(!links_to(&html, "tt0071562", &profile).unwrap());
+        assert!(
+            links_to(
+                &page("http://imdb.com/title/TT0068646"),
+                "tt0068646",
+                &profile
+            )
+            .unwrap()
+        );
+
+        assert!(matches!(
+            links_to("<html><body></body></html>", "tt0068646", &profile),
+            Err(crate::Error::LayoutChanged(..))
+        ));
+    }
+}
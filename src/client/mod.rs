@@ -1,7 +1,30 @@
 #[cfg(feature = "blocking")]
 pub mod blocking;
 
+mod cache;
+mod cassette;
+mod crawl;
 pub mod default;
+mod disk_cache;
+mod download;
+mod imdb;
+mod observer;
 mod parameter;
+mod post_filter;
+mod proxy;
+mod rate_limit;
+mod scrape;
+mod self_test;
 
+pub use cache::{Cache, CacheStats};
+pub use cassette::{Cassette, CassetteMode};
+pub use crawl::{Crawl, CrawlLimits, CrawlStats};
+pub use disk_cache::DiskCache;
+pub use download::DownloadedTorrent;
+pub use observer::{CacheKind, Endpoint, Observer};
 pub use parameter::*;
+pub use post_filter::PostFilter;
+pub use proxy::{Proxies, Proxy};
+pub use rate_limit::RateLimiter;
+pub use scrape::{Scrape, ScrapeLimits, SwarmHealth};
+pub use self_test::SelfTest;
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+/// Kind of page requested from the website.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// A browse page listing movies.
+    Browse,
+    /// A movie page listing torrents.
+    Movie,
+    /// A `.torrent` file.
+    Torrent,
+}
+
+/// Cache that answered a page lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheKind {
+    /// The in-memory [`Cache`](crate::Cache).
+    Memory,
+    /// The [`DiskCache`](crate::DiskCache), offline or revalidated with `304 Not Modified`.
+    Disk,
+}
+
+/// Receives events from a client, to feed metrics such as Prometheus counters and
+/// histograms.
+///
+/// Every method has an empty default, so an implementation only overrides what it needs.
+/// Methods are called on the task or thread sending the request and should return quickly.
+///
+/// # Examples
+///
+/// ```
+/// use std::{
+///     sync::atomic::{AtomicU64, Ordering},
+///     time::Duration,
+/// };
+/// use yts_movies::{Endpoint, Observer, Yts};
+///
+/// #[derive(Default)]
+/// struct Requests(AtomicU64);
+///
+/// impl Observer for Requests {
+///     fn on_response(&self, _: Endpoint, _: u16, _: Duration, _: usize) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let yts = Yts::default().with_observer(Requests::default());
+/// ```
+pub trait Observer: Send + Sync {
+    /// Called for every response received from the network, whatever its status.
+    ///
+    /// # Parameters
+    /// - `endpoint`: Kind of page requested.
+    /// - `status`: HTTP status code.
+    /// - `latency`: Time from sending the request to reading the whole body, excluding
+    ///   rate limiting.
+    /// - `bytes`: Size of the body.
+    fn on_response(&self, endpoint: Endpoint, status: u16, latency: Duration, bytes: usize) {
+        let _ = (endpoint, status, latency, bytes);
+    }
+
+    /// Called when a request fails without a response, e.g. on a timeout or a proxy error.
+    fn on_request_error(&self, endpoint: Endpoint, error: &crate::Error) {
+        let _ = (endpoint, error);
+    }
+
+    /// Called when a page is served from a cache.
+    fn on_cache_hit(&self, endpoint: Endpoint, cache: CacheKind) {
+        let _ = (endpoint, cache);
+    }
+
+    /// Called when a page cannot be parsed. [`Error::name`](crate::Error::name) gives a
+    /// stable label for the failure.
+    fn on_parse_error(&self, endpoint: Endpoint, error: &crate::Error) {
+        let _ = (endpoint, error);
+    }
+}
+
+impl std::fmt::Debug for dyn Observer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Observer")
+    }
+}
+
+impl Endpoint {
+    /// Returns a stable label for the endpoint, e.g. `browse`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Endpoint::Browse => "browse",
+            Endpoint::Movie => "movie",
+            Endpoint::Torrent => "torrent",
+        }
+    }
+}
+
+impl CacheKind {
+    /// Returns a stable label for the cache, e.g. `memory`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CacheKind::Memory => "memory",
+            CacheKind::Disk => "disk",
+        }
+    }
+}
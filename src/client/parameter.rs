@@ -1,14 +1,18 @@
-use crate::Genre;
+use std::str::FromStr;
+
+use reqwest::Url;
+
+use crate::{Genre, Response};
 
 /// Builder pattern struct for configuring movie filters.
 ///
 /// # Examples
 ///
 /// ```
-/// use yts_movies::{Filters, Genre, Quality, Rating, Year, OrderBy};
+/// use yts_movies::{Filters, Genre, Quality, Rating, Resolution, Year, OrderBy};
 ///
 /// let filters = Filters::default()
-///     .quality(Quality::P1080)
+///     .quality(Quality::new(Resolution::P1080))
 ///     .genre(Genre::Action)
 ///     .rating(Rating::Seven)
 ///     .year(Year::Range2000to2009)
@@ -23,19 +27,23 @@ impl Default for Filters {
     /// Creates a `Filters` builder with default filter parameters.
     ///
     /// Defaults:
-    /// - quality: `Quality::All`
+    /// - quality: `None` (all qualities)
     /// - genre: `Genre::All`
     /// - rating: `Rating::All`
     /// - year: `Year::All`
     /// - order_by: `OrderBy::Latest`
+    /// - direction: `Direction::Descending`
+    /// - language: `None` (all languages)
     /// - page: 1
     fn default() -> Self {
         Self(Filter {
-            quality: Quality::All,
+            quality: None,
             genre: Genre::All,
             rating: Rating::All,
             year: Year::All,
             order_by: OrderBy::Latest,
+            direction: Direction::Descending,
+            language: None,
             page: 1,
         })
     }
@@ -43,8 +51,8 @@ impl Default for Filters {
 
 impl Filters {
     /// Sets the quality filter.
-    pub fn quality(mut self, quality: Quality) -> Self {
-        self.0.quality = quality;
+    pub fn quality(mut self, quality: impl Into<Quality>) -> Self {
+        self.0.quality = Some(quality.into());
         self
     }
 
@@ -72,6 +80,18 @@ impl Filters {
         self
     }
 
+    /// Sets the sorting direction.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.0.direction = direction;
+        self
+    }
+
+    /// Sets the language filter, as the site's language code (e.g. `en`, `fr`).
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.0.language = Some(language.into());
+        self
+    }
+
     /// Sets the page.
     pub fn page(mut self, page: u32) -> Self {
         self.0.page = page;
@@ -87,10 +107,31 @@ impl Filters {
 /// Represents the finalized set of filters applied to movie queries.
 ///
 /// This struct contains all filter parameters as concrete values.
-#[derive(Debug)]
+///
+/// A filter can be converted to and from a browse URL of the website.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::{Filters, Genre};
+///
+/// let filter = Filters::default().genre(Genre::Crime).page(3).build();
+/// let url = filter.to_url("https://yts.lt", "the godfather")?;
+/// assert_eq!(
+///     url,
+///     "https://yts.lt/browse-movies/the%20godfather/all/crime/0/latest/0/all?page=3"
+/// );
+///
+/// let (query, parsed) = yts_movies::Filter::from_url(&url)?;
+/// assert_eq!(query, "the godfather");
+/// assert_eq!(parsed, filter);
+/// # Ok::<(), yts_movies::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Filter {
-    /// Quality filter.
-    pub quality: Quality,
+    /// Quality filter. `None` matches all qualities.
+    pub quality: Option<Quality>,
     /// Genre filter.
     pub genre: Genre,
     /// Rating filter.
@@ -99,6 +140,10 @@ pub struct Filter {
     pub year: Year,
     /// Sorting order.
     pub order_by: OrderBy,
+    /// Sorting direction.
+    pub direction: Direction,
+    /// Language code filter. `None` matches all languages.
+    pub language: Option<String>,
     /// Page number for pagination.
     pub page: u32,
 }
@@ -106,7 +151,7 @@ pub struct Filter {
 impl Filter {
     /// Converts the quality filter to its string representation.
     pub fn quality_to_str(&self) -> &str {
-        (&self.quality).into()
+        self.quality.as_ref().map_or("all", Quality::slug)
     }
 
     /// Converts the genre filter to its string representation.
@@ -121,22 +166,279 @@ impl Filter {
 
     /// Converts the year filter to its string representation.
     ///
-    /// Returns a `String` because some year variants represent ranges.
+    /// Returns a `String` because some year variants represent ranges. Custom ranges
+    /// are converted to the natively supported filter that covers them.
     pub fn year_to_str(&self) -> String {
-        (&self.year).into()
+        (&self.year.browse()).into()
+    }
+
+    /// Converts the language filter to its string representation.
+    pub fn language_to_str(&self) -> &str {
+        self.language.as_deref().unwrap_or("all")
+    }
+
+    /// Builds the browse URL for `query` on `host` with this filter.
+    ///
+    /// The URL holds the filter exactly, so [`Filter::from_url`] gives it back. Filters
+    /// the URL cannot hold are rejected: a quality with a source or codec the site does
+    /// not filter on (only `1080p.x265` exists), a custom [`Year::Range`] that is not a
+    /// preset or a single year, and [`Direction::Ascending`], which the site expresses as
+    /// the opposite date order or not at all. Searches still accept those filters,
+    /// fetching the bare resolution, the covering preset or the opposite order, or
+    /// sorting on the client side.
+    ///
+    /// # Parameters
+    /// - `host`: Base URL of the website (e.g. `https://yts.lt`).
+    /// - `query`: The movie name or keyword to search for.
+    ///
+    /// # Returns
+    /// A `String` containing the fully constructed URL.
+    ///
+    /// # Errors
+    /// Returns an error if the host is not a valid URL or the filter is invalid, see
+    /// [`Filter::validate`], and [`Error::NoBrowseUrl`](crate::Error::NoBrowseUrl) if
+    /// the URL cannot hold the filter.
+    pub fn to_url(&self, host: &str, query: &str) -> crate::Result<String> {
+        self.validate()?;
+
+        if self.direction == Direction::Ascending {
+            let reason = match self.order_by {
+                OrderBy::Latest => "order by oldest instead",
+                OrderBy::Oldest => "order by latest instead",
+                _ => "sorted on the client side",
+            };
+            return Err(crate::Error::NoBrowseUrl(format!(
+                "ascending direction, {reason}"
+            )));
+        }
+        if let Some(quality) = &self.quality
+            && Quality::from_slug(quality.slug()).as_ref() != Some(quality)
+        {
+            return Err(crate::Error::NoBrowseUrl(format!(
+                "quality {quality}, searched as {}",
+                quality.slug()
+            )));
+        }
+        if self.year.browse() != self.year {
+            return Err(crate::Error::NoBrowseUrl(format!(
+                "year range {}, searched as {}",
+                String::from(&self.year),
+                self.year_to_str()
+            )));
+        }
+
+        self.browse_url(host, query)
+    }
+
+    /// Builds the URL fetched when searching `query` on `host` with this filter.
+    ///
+    /// Unlike [`Filter::to_url`], custom year ranges are sent as the covering preset and
+    /// an ascending date order as its opposite.
+    ///
+    /// # Errors
+    /// Returns an error if the host is not a valid URL or the filter is invalid.
+    pub(crate) fn browse_url(&self, host: &str, query: &str) -> crate::Result<String> {
+        self.validate()?;
+
+        let url = Url::parse(&format!("{}/browse-movies/{}", host, query.trim()))
+            .map_err(|_| crate::Error::ParseError(host.to_string()))?;
+
+        let page = if self.page <= 1 {
+            String::new()
+        } else {
+            format!("?page={}", self.page)
+        };
+
+        Ok(format!(
+            "{}/{}/{}/{}/{}/{}/{}{}",
+            url.as_str(),
+            self.quality_to_str(),
+            self.genre_to_str(),
+            self.rating_to_str(),
+            self.order_by_to_str(),
+            self.year_to_str(),
+            self.language_to_str(),
+            page
+        ))
+    }
+
+    /// Parses a browse URL of the website into its query and filter.
+    ///
+    /// This is the inverse of [`Filter::to_url`]. Trailing segments missing from the URL
+    /// take their default values, so `https://yts.lt/browse-movies` is a valid input.
+    ///
+    /// # Parameters
+    /// - `url`: A browse URL such as `https://yts.lt/browse-movies/heat/1080p/crime/7/rating/1995/en`.
+    ///
+    /// # Returns
+    /// A `Result` containing the decoded search query and the parsed `Filter`.
+    ///
+    /// # Errors
+    /// Returns [`Error::ParseError`](crate::Error::ParseError) if `url` is not a URL, and
+    /// [`Error::BrowseUrlError`](crate::Error::BrowseUrlError) naming the offending part
+    /// if it is not a browse URL or has an unknown segment.
+    pub fn from_url(url: &str) -> crate::Result<(String, Filter)> {
+        let parsed =
+            Url::parse(url.trim()).map_err(|_| crate::Error::ParseError(url.to_string()))?;
+        let invalid = |message: String| crate::Error::BrowseUrlError(message);
+
+        let path = parsed.path().trim_end_matches('/');
+        let mut segments = path.trim_start_matches('/').split('/');
+
+        if segments.next() != Some("browse-movies") {
+            return Err(invalid(format!("not a browse url: {url}")));
+        }
+
+        let mut filter = Filters::default().build();
+
+        let query = segments.next().map(percent_decode).unwrap_or_default();
+        let query = if query == "0" { String::new() } else { query };
+
+        if let Some(quality) = segments.next() {
+            filter.quality = match quality {
+                "all" => None,
+                quality => Some(
+                    Quality::from_slug(quality)
+                        .ok_or_else(|| invalid(format!("Invalid value for Quality: {quality}")))?,
+                ),
+            };
+        }
+        if let Some(genre) = segments.next() {
+            filter.genre = Genre::from_slug(genre)
+                .ok_or_else(|| invalid(format!("Invalid value for Genre: {genre}")))?;
+        }
+        if let Some(rating) = segments.next() {
+            filter.rating = Rating::try_from(rating).map_err(invalid)?;
+        }
+        if let Some(order_by) = segments.next() {
+            filter.order_by = OrderBy::try_from(order_by).map_err(invalid)?;
+        }
+        if let Some(year) = segments.next() {
+            filter.year = Year::try_from(year).map_err(invalid)?;
+        }
+        if let Some(language) = segments.next() {
+            filter.language = match language {
+                "all" => None,
+                language if language.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                    Some(language.to_string())
+                }
+                language => return Err(invalid(format!("Invalid value for language: {language}"))),
+            };
+        }
+        if let Some(extra) = segments.next() {
+            return Err(invalid(format!("unexpected segment: {extra}")));
+        }
+
+        if let Some((_, page)) = parsed.query_pairs().find(|(key, _)| key == "page") {
+            filter.page = page
+                .parse()
+                .map_err(|_| invalid(format!("Invalid value for page: {page}")))?;
+        }
+
+        Ok((query, filter))
+    }
+
+    /// Checks that the filter is something the site can serve.
+    ///
+    /// # Errors
+    /// Returns [`Error::YearRangeError`](crate::Error::YearRangeError) if the year filter
+    /// is invalid, see [`Year::validate`].
+    pub fn validate(&self) -> crate::Result {
+        self.year.validate()
+    }
+
+    /// Returns `true` if the direction is applied on the client side: ascending, on an
+    /// order the site only sorts descending.
+    pub(crate) fn sorts_on_client(&self) -> bool {
+        self.direction == Direction::Ascending
+            && !matches!(self.order_by, OrderBy::Latest | OrderBy::Oldest)
+    }
+
+    /// Returns the filter to crawl every page with, and whether to reverse the merged
+    /// movies: an ascending direction sorted on the client side is fetched descending.
+    pub(crate) fn for_crawl(self) -> (Filter, bool) {
+        if !self.sorts_on_client() {
+            return (self, false);
+        }
+        let descending = Filter {
+            direction: Direction::Descending,
+            ..self
+        };
+        (descending, true)
+    }
+
+    /// Applies the parts of the filter the browse page cannot express.
+    ///
+    /// - Movies outside a custom year span are dropped, since the page was fetched with
+    ///   a broader range. Pagination info still describes the broader fetch, see
+    ///   [`Year`].
+    /// - An ascending direction on an order the site only sorts descending reverses the
+    ///   movies of the page, see [`Direction`].
+    pub(crate) fn apply_to(&self, response: &mut Response) {
+        if !self.year.is_native() {
+            response
+                .movies
+                .retain(|movie| self.year.contains(movie.year));
+        }
+
+        if self.sorts_on_client() {
+            response.movies.reverse();
+        }
     }
 
     /// Converts the order_by filter to its string representation.
+    ///
+    /// An ascending `Latest` or `Oldest` order is sent as its opposite, since the site
+    /// models that direction natively.
     pub fn order_by_to_str(&self) -> &str {
-        (&self.order_by).into()
+        match (&self.order_by, &self.direction) {
+            (OrderBy::Latest, Direction::Ascending) => "oldest",
+            (OrderBy::Oldest, Direction::Ascending) => "latest",
+            (order_by, _) => order_by.into(),
+        }
     }
 }
 
-/// Represents video quality filter options.
-#[derive(Debug, Clone)]
-pub enum Quality {
-    /// All qualities.
-    All,
+impl FromStr for Filter {
+    type Err = crate::Error;
+
+    /// Parses a browse URL into a `Filter`, discarding the search query.
+    ///
+    /// Use [`Filter::from_url`] to keep the query.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_url(s).map(|(_, filter)| filter)
+    }
+}
+
+/// Decodes the percent-encoded bytes of an URL path segment.
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Represents the video resolution of a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// 480p resolution.
+    P480,
     /// 720p resolution.
     P720,
     /// 1080p resolution.
@@ -147,41 +449,223 @@ pub enum Quality {
     ThreeD,
 }
 
-impl From<&Quality> for &str {
-    fn from(value: &Quality) -> Self {
+impl From<&Resolution> for &str {
+    fn from(value: &Resolution) -> Self {
+        match value {
+            Resolution::P480 => "480p",
+            Resolution::P720 => "720p",
+            Resolution::P1080 => "1080p",
+            Resolution::P2160 => "2160p",
+            Resolution::ThreeD => "3D",
+        }
+    }
+}
+
+impl TryFrom<&str> for Resolution {
+    type Error = String;
+
+    /// Parses a resolution such as `1080p` or `3D`, ignoring case.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "480p" => Ok(Resolution::P480),
+            "720p" => Ok(Resolution::P720),
+            "1080p" => Ok(Resolution::P1080),
+            "2160p" => Ok(Resolution::P2160),
+            "3d" => Ok(Resolution::ThreeD),
+            _ => Err(format!("Invalid value for Resolution: {value}")),
+        }
+    }
+}
+
+/// Represents the video codec of a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// x264 (H.264 / AVC) encode.
+    X264,
+    /// x265 (H.265 / HEVC) encode.
+    X265,
+}
+
+impl From<&Codec> for &str {
+    fn from(value: &Codec) -> Self {
+        match value {
+            Codec::X264 => "x264",
+            Codec::X265 => "x265",
+        }
+    }
+}
+
+impl TryFrom<&str> for Codec {
+    type Error = String;
+
+    /// Parses a codec such as `x265` or `hevc`, ignoring case.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "x264" | "h264" => Ok(Codec::X264),
+            "x265" | "h265" | "hevc" => Ok(Codec::X265),
+            _ => Err(format!("Invalid value for Codec: {value}")),
+        }
+    }
+}
+
+/// Represents the source a release was encoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Blu-ray disc source.
+    BluRay,
+    /// Web (streaming service) source.
+    Web,
+}
+
+impl From<&Source> for &str {
+    fn from(value: &Source) -> Self {
         match value {
-            Quality::All => "all",
-            Quality::P720 => "720p",
-            Quality::P1080 => "1080p",
-            Quality::P2160 => "2160p",
-            Quality::ThreeD => "3D",
+            Source::BluRay => "BluRay",
+            Source::Web => "WEB",
+        }
+    }
+}
+
+impl TryFrom<&str> for Source {
+    type Error = String;
+
+    /// Parses a source such as `BluRay` or `WEB`, ignoring case.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "bluray" => Ok(Source::BluRay),
+            "web" | "webrip" | "web-dl" => Ok(Source::Web),
+            _ => Err(format!("Invalid value for Source: {value}")),
         }
     }
 }
 
-impl From<&str> for Quality {
-    /// Converts a string slice to a `Quality` variant.
+/// Represents video quality: a resolution plus an optional codec and source.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::{Codec, Quality, Resolution, Source};
+///
+/// let quality = Quality::new(Resolution::P1080).codec(Codec::X265);
+/// assert_eq!(quality.to_string(), "1080p.x265");
+///
+/// let quality = Quality::try_from("1080p.WEB").unwrap();
+/// assert_eq!(quality.source, Some(Source::Web));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quality {
+    /// Video resolution.
+    pub resolution: Resolution,
+    /// Video codec, if known.
+    pub codec: Option<Codec>,
+    /// Release source, if known.
+    pub source: Option<Source>,
+}
+
+impl Quality {
+    /// Creates a `Quality` with the given resolution and no codec or source.
+    pub fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            codec: None,
+            source: None,
+        }
+    }
+
+    /// Sets the codec.
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = Some(codec);
+        self
+    }
+
+    /// Sets the source.
+    pub fn source(mut self, source: Source) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Returns the slug used by the browse page for this quality.
+    ///
+    /// The site only filters on resolution, plus `1080p.x265`. Any source, or a codec
+    /// on other resolutions, is dropped from the slug.
+    pub(crate) fn slug(&self) -> &'static str {
+        match (self.resolution, self.codec) {
+            (Resolution::P1080, Some(Codec::X265)) => "1080p.x265",
+            (resolution, _) => (&resolution).into(),
+        }
+    }
+
+    /// Parses a quality slug of the browse page, e.g. `720p` or `1080p.x265`.
     ///
-    /// The conversion matches substrings "720", "1080", or "2160" to corresponding variants.
-    /// Any other string defaults to `ThreeD`.
-    fn from(value: &str) -> Self {
-        if value.contains("720") {
-            return Self::P720;
+    /// Returns `None` for any other label, e.g. `720p.BluRay`, which the site never puts
+    /// in a browse URL.
+    pub(crate) fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "1080p.x265" => Some(Quality::new(Resolution::P1080).codec(Codec::X265)),
+            slug => Resolution::try_from(slug).ok().map(Quality::new),
         }
-        if value.contains("1080") {
-            return Self::P1080;
+    }
+}
+
+impl From<Resolution> for Quality {
+    fn from(value: Resolution) -> Self {
+        Self::new(value)
+    }
+}
+
+impl std::fmt::Display for Quality {
+    /// Formats the quality the way the site labels torrents, e.g. `1080p.WEB.x265`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let resolution: &str = (&self.resolution).into();
+        write!(f, "{resolution}")?;
+        if let Some(source) = &self.source {
+            let source: &str = source.into();
+            write!(f, ".{source}")?;
         }
-        if value.contains("2160") {
-            return Self::P2160;
+        if let Some(codec) = &self.codec {
+            let codec: &str = codec.into();
+            write!(f, ".{codec}")?;
         }
-        Self::ThreeD
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for Quality {
+    type Error = String;
+
+    /// Parses a quality label such as `720p`, `1080p.WEB` or `2160p.BluRay.x265`.
+    ///
+    /// Labels are split on `.` and each part is matched case-insensitively. A label
+    /// without a resolution or with an unknown part is rejected.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut resolution = None;
+        let mut codec = None;
+        let mut source = None;
+
+        for part in value.trim().split('.') {
+            if let Ok(value) = Resolution::try_from(part) {
+                resolution = Some(value);
+            } else if let Ok(value) = Codec::try_from(part) {
+                codec = Some(value);
+            } else if let Ok(value) = Source::try_from(part) {
+                source = Some(value);
+            } else {
+                return Err(format!("Invalid value for Quality: {value}"));
+            }
+        }
+
+        Ok(Self {
+            resolution: resolution.ok_or_else(|| format!("Invalid value for Quality: {value}"))?,
+            codec,
+            source,
+        })
     }
 }
 
 /// Represents rating filter options.
 ///
 /// Ratings from 0 (All) to 9.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Rating {
     All,
     /// Represents 1+
@@ -221,15 +705,54 @@ impl From<&Rating> for &str {
     }
 }
 
+impl TryFrom<&str> for Rating {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "0" => Ok(Rating::All),
+            "1" => Ok(Rating::One),
+            "2" => Ok(Rating::Two),
+            "3" => Ok(Rating::Three),
+            "4" => Ok(Rating::Four),
+            "5" => Ok(Rating::Five),
+            "6" => Ok(Rating::Six),
+            "7" => Ok(Rating::Seven),
+            "8" => Ok(Rating::Eight),
+            "9" => Ok(Rating::Nine),
+            _ => Err(format!("Invalid value for Rating: {value}")),
+        }
+    }
+}
+
 /// Represents year filter options.
 ///
 /// Includes specific years, ranges, or all years.
-#[derive(Debug, Clone)]
+///
+/// The browse page only understands single years and the preset ranges. A custom
+/// [`Year::Range`] is sent as the narrowest preset covering it and the results are
+/// trimmed to the requested span on the client side.
+///
+/// The pagination of such a search still describes the covering preset: [`Page::total`]
+/// and [`Page::of`] count the movies of the whole preset, and a page may hold no movie
+/// while [`Page::next`] is set. Follow `next` until it is `None` rather than stopping at
+/// the first empty page.
+///
+/// [`Page::total`]: crate::Page::total
+/// [`Page::of`]: crate::Page::of
+/// [`Page::next`]: crate::Page::next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Year {
     /// All years.
     All,
     /// Exact year.
     Equal(u32),
+    /// Custom inclusive year range (`from`, `to`).
+    Range(u32, u32),
+    /// Year range 2020-2029.
+    Range2020to2029,
+    /// Year range 2010-2019.
+    Range2010to2019,
     /// Year range 2000-2009.
     Range2000to2009,
     /// Year range 1990-1999.
@@ -244,11 +767,105 @@ pub enum Year {
     Range1900to1949,
 }
 
+impl Year {
+    /// Earliest year accepted by the site.
+    pub const MIN: u32 = 1900;
+
+    /// Preset ranges supported natively by the browse page.
+    const PRESETS: [Year; 8] = [
+        Year::Range1900to1949,
+        Year::Range1950to1969,
+        Year::Range1970to1979,
+        Year::Range1980to1989,
+        Year::Range1990to1999,
+        Year::Range2000to2009,
+        Year::Range2010to2019,
+        Year::Range2020to2029,
+    ];
+
+    /// Returns the inclusive bounds of this year filter, or `None` for `Year::All`.
+    pub fn bounds(&self) -> Option<(u32, u32)> {
+        match self {
+            Year::All => None,
+            Year::Equal(year) => Some((*year, *year)),
+            Year::Range(from, to) => Some((*from, *to)),
+            Year::Range2020to2029 => Some((2020, 2029)),
+            Year::Range2010to2019 => Some((2010, 2019)),
+            Year::Range2000to2009 => Some((2000, 2009)),
+            Year::Range1990to1999 => Some((1990, 1999)),
+            Year::Range1980to1989 => Some((1980, 1989)),
+            Year::Range1970to1979 => Some((1970, 1979)),
+            Year::Range1950to1969 => Some((1950, 1969)),
+            Year::Range1900to1949 => Some((1900, 1949)),
+        }
+    }
+
+    /// Returns `true` if `year` falls within this filter.
+    pub fn contains(&self, year: u32) -> bool {
+        self.bounds()
+            .is_none_or(|(from, to)| (from..=to).contains(&year))
+    }
+
+    /// Returns `true` if the browse page can apply this filter directly.
+    pub fn is_native(&self) -> bool {
+        self.browse().bounds() == self.bounds()
+    }
+
+    /// Returns the natively supported filter used to build the browse URL.
+    ///
+    /// Custom ranges map to a single year, the narrowest covering preset, or
+    /// `Year::All` when no preset covers them.
+    pub(crate) fn browse(&self) -> Year {
+        match *self {
+            Year::Range(from, to) if from == to => Year::Equal(from),
+            Year::Range(from, to) => Self::PRESETS
+                .into_iter()
+                .find(|preset| preset.contains(from) && preset.contains(to))
+                .unwrap_or(Year::All),
+            year => year,
+        }
+    }
+
+    /// Checks that the year filter is something the site can serve.
+    ///
+    /// Presets are always valid, even when they reach into future years. Other filters may
+    /// reach next year, for announced titles.
+    ///
+    /// # Errors
+    /// Returns [`Error::YearRangeError`](crate::Error::YearRangeError) if a year is
+    /// before [`Year::MIN`] or after next year, or a range ends before it starts.
+    pub fn validate(&self) -> crate::Result {
+        let (from, to) = match *self {
+            Year::Equal(year) => (year, year),
+            Year::Range(from, to) => (from, to),
+            _ => return Ok(()),
+        };
+        if from < Self::MIN || from > to || to > Self::current() + 1 {
+            return Err(crate::Error::YearRangeError(from, to));
+        }
+        Ok(())
+    }
+
+    /// Returns the current year in UTC, from the system clock.
+    pub(crate) fn current() -> u32 {
+        Self::year_at(time::OffsetDateTime::now_utc())
+    }
+
+    /// Returns the year of `moment` in UTC.
+    fn year_at(moment: time::OffsetDateTime) -> u32 {
+        let year = moment.to_offset(time::UtcOffset::UTC).year();
+        u32::try_from(year).unwrap_or_default()
+    }
+}
+
 impl From<&Year> for String {
     fn from(value: &Year) -> Self {
         match value {
             Year::All => "0".to_string(),
             Year::Equal(year) => year.to_string(),
+            Year::Range(from, to) => format!("{from}-{to}"),
+            Year::Range2020to2029 => "2020-2029".to_string(),
+            Year::Range2010to2019 => "2010-2019".to_string(),
             Year::Range2000to2009 => "2000-2009".to_string(),
             Year::Range1990to1999 => "1990-1999".to_string(),
             Year::Range1980to1989 => "1980-1989".to_string(),
@@ -259,8 +876,38 @@ impl From<&Year> for String {
     }
 }
 
+impl TryFrom<&str> for Year {
+    type Error = String;
+
+    /// Parses a year segment: `0` for all years, a single year, or a `from-to` range.
+    ///
+    /// Ranges matching a preset become that preset variant.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let invalid = || format!("Invalid value for Year: {value}");
+
+        if value == "0" {
+            return Ok(Year::All);
+        }
+
+        let year = match value.split_once('-') {
+            None => Year::Equal(value.parse().map_err(|_| invalid())?),
+            Some((from, to)) => {
+                let from = from.parse().map_err(|_| invalid())?;
+                let to = to.parse().map_err(|_| invalid())?;
+                Year::PRESETS
+                    .into_iter()
+                    .find(|preset| preset.bounds() == Some((from, to)))
+                    .unwrap_or(Year::Range(from, to))
+            }
+        };
+
+        year.validate().map_err(|_| invalid())?;
+        Ok(year)
+    }
+}
+
 /// Represents ordering options for movie queries.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderBy {
     /// Sort by latest.
     Latest,
@@ -276,6 +923,12 @@ pub enum OrderBy {
     Likes,
     /// Sort alphabetically.
     Alphabetical,
+    /// Sort by number of seeds.
+    Seeds,
+    /// Sort by number of peers.
+    Peers,
+    /// Sort by number of downloads.
+    Downloads,
 }
 
 impl From<&OrderBy> for &str {
@@ -288,6 +941,9 @@ impl From<&OrderBy> for &str {
             OrderBy::Rating => "rating",
             OrderBy::Likes => "likes",
             OrderBy::Alphabetical => "alphabetical",
+            OrderBy::Seeds => "seeds",
+            OrderBy::Peers => "peers",
+            OrderBy::Downloads => "downloads",
         }
     }
 }
@@ -304,7 +960,412 @@ impl TryFrom<&str> for OrderBy {
             "rating" => Ok(OrderBy::Rating),
             "likes" => Ok(OrderBy::Likes),
             "alphabetical" => Ok(OrderBy::Alphabetical),
+            "seeds" => Ok(OrderBy::Seeds),
+            "peers" => Ok(OrderBy::Peers),
+            "downloads" => Ok(OrderBy::Downloads),
             _ => Err(format!("Invalid value for OrderBy: {value}")),
         }
     }
 }
+
+/// Represents the sorting direction of movie queries.
+///
+/// The site sorts by date both ways, so an ascending [`OrderBy::Latest`] or
+/// [`OrderBy::Oldest`] is fetched as the opposite order. Every other order is only served
+/// descending and is sorted ascending on the client side:
+///
+/// - `search_all` fetches the pages descending and reverses the merged movies, so the
+///   whole crawl is in ascending order.
+/// - `search_with_filter` and `search_until` reverse each page they fetch. Pages are
+///   still counted from the top of the site's order, so page 1 holds the highest ranked
+///   movies, lowest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Highest, newest or last first. This is the site's default.
+    Descending,
+    /// Lowest, oldest or first first.
+    Ascending,
+}
+
+impl From<&Direction> for &str {
+    fn from(value: &Direction) -> Self {
+        match value {
+            Direction::Descending => "desc",
+            Direction::Ascending => "asc",
+        }
+    }
+}
+
+impl TryFrom<&str> for Direction {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "desc" => Ok(Direction::Descending),
+            "asc" => Ok(Direction::Ascending),
+            _ => Err(format!("Invalid value for Direction: {value}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Codec, Direction, Filter, Filters, OrderBy, Quality, Rating, Resolution, Source, Year,
+    };
+    use crate::{Genre, Page, Response};
+
+    #[test]
+    fn test_quality_try_from() {
+        let quality = Quality::try_from("1080p.WEB").unwrap();
+        assert_eq!(quality.resolution, Resolution::P1080);
+        assert_eq!(quality.source, Some(Source::Web));
+        assert_eq!(quality.codec, None);
+
+        let quality = Quality::try_from("2160p.BluRay.x265").unwrap();
+        assert_eq!(quality.resolution, Resolution::P2160);
+        assert_eq!(quality.source, Some(Source::BluRay));
+        assert_eq!(quality.codec, Some(Codec::X265));
+
+        assert_eq!(
+            Quality::try_from("3D").unwrap(),
+            Quality::new(Resolution::ThreeD)
+        );
+        assert!(Quality::try_from("1440p").is_err());
+        assert!(Quality::try_from("WEB").is_err());
+        assert!(Quality::try_from("").is_err());
+    }
+
+    #[test]
+    fn test_quality_display_round_trip() {
+        for label in ["480p", "720p.BluRay", "1080p.WEB.x265", "2160p.x265", "3D"] {
+            let quality = Quality::try_from(label).unwrap();
+            assert_eq!(quality.to_string(), label);
+        }
+    }
+
+    #[test]
+    fn test_quality_to_str() {
+        assert_eq!(Filters::default().build().quality_to_str(), "all");
+        assert_eq!(
+            Filters::default()
+                .quality(Resolution::P720)
+                .build()
+                .quality_to_str(),
+            "720p"
+        );
+        assert_eq!(
+            Filters::default()
+                .quality(Quality::new(Resolution::P1080).codec(Codec::X265))
+                .build()
+                .quality_to_str(),
+            "1080p.x265"
+        );
+        assert_eq!(
+            Filters::default()
+                .quality(Quality::new(Resolution::P2160).source(Source::Web))
+                .build()
+                .quality_to_str(),
+            "2160p"
+        );
+    }
+
+    #[test]
+    fn test_year_browse() {
+        assert_eq!(Year::Range(1972, 1972).browse(), Year::Equal(1972));
+        assert_eq!(Year::Range(1971, 1975).browse(), Year::Range1970to1979);
+        assert_eq!(Year::Range(2011, 2019).browse(), Year::Range2010to2019);
+        assert_eq!(Year::Range(2021, 2024).browse(), Year::Range2020to2029);
+        assert_eq!(Year::Range(1985, 1995).browse(), Year::All);
+        assert_eq!(Year::Range2000to2009.browse(), Year::Range2000to2009);
+
+        assert_eq!(
+            Filters::default()
+                .year(Year::Range(1971, 1975))
+                .build()
+                .year_to_str(),
+            "1970-1979"
+        );
+    }
+
+    #[test]
+    fn test_year_is_native() {
+        assert!(Year::All.is_native());
+        assert!(Year::Equal(1999).is_native());
+        assert!(Year::Range2010to2019.is_native());
+        assert!(Year::Range(1980, 1989).is_native());
+        assert!(Year::Range(2005, 2005).is_native());
+        assert!(!Year::Range(1981, 1989).is_native());
+        assert!(!Year::Range(1985, 1995).is_native());
+    }
+
+    #[test]
+    fn test_year_validate() {
+        assert!(Year::All.validate().is_ok());
+        assert!(Year::Range(1990, 2005).validate().is_ok());
+        assert!(Year::Range(2005, 1990).validate().is_err());
+        assert!(Year::Range(1850, 1960).validate().is_err());
+        assert!(Year::Equal(1899).validate().is_err());
+
+        let current = Year::current();
+        assert!(current >= 2024);
+        assert!(Year::Equal(current).validate().is_ok());
+        assert!(Year::Equal(current + 1).validate().is_ok());
+        assert!(Year::Range(current, current + 1).validate().is_ok());
+        assert!(Year::Equal(current + 2).validate().is_err());
+        assert!(Year::Range(2020, current + 5).validate().is_err());
+        assert!(Year::Range2020to2029.validate().is_ok());
+    }
+
+    #[test]
+    fn test_year_at_rollover() {
+        let at = |timestamp| {
+            Year::year_at(time::OffsetDateTime::from_unix_timestamp(timestamp).unwrap())
+        };
+
+        assert_eq!(at(0), 1970);
+        assert_eq!(at(1_735_689_599), 2024);
+        assert_eq!(at(1_735_689_600), 2025);
+        assert_eq!(at(951_782_400), 2000);
+
+        let new_year = time::OffsetDateTime::from_unix_timestamp(1_735_689_600)
+            .unwrap()
+            .to_offset(time::UtcOffset::from_hms(-5, 0, 0).unwrap());
+        assert_eq!(Year::year_at(new_year), 2025);
+    }
+
+    #[test]
+    fn test_order_by_round_trip() {
+        for order_by in [
+            OrderBy::Latest,
+            OrderBy::Oldest,
+            OrderBy::Featured,
+            OrderBy::Year,
+            OrderBy::Rating,
+            OrderBy::Likes,
+            OrderBy::Alphabetical,
+            OrderBy::Seeds,
+            OrderBy::Peers,
+            OrderBy::Downloads,
+        ] {
+            let value: &str = (&order_by).into();
+            assert_eq!(OrderBy::try_from(value), Ok(order_by));
+        }
+        assert!(OrderBy::try_from("popularity").is_err());
+    }
+
+    #[test]
+    fn test_direction_round_trip() {
+        for direction in [Direction::Descending, Direction::Ascending] {
+            let value: &str = (&direction).into();
+            assert_eq!(Direction::try_from(value), Ok(direction));
+        }
+        assert!(Direction::try_from("up").is_err());
+    }
+
+    #[test]
+    fn test_order_by_to_str_with_direction() {
+        let order_by_to_str = |order_by, direction| {
+            Filters::default()
+                .order_by(order_by)
+                .direction(direction)
+                .build()
+                .order_by_to_str()
+                .to_string()
+        };
+
+        assert_eq!(
+            order_by_to_str(OrderBy::Latest, Direction::Descending),
+            "latest"
+        );
+        assert_eq!(
+            order_by_to_str(OrderBy::Latest, Direction::Ascending),
+            "oldest"
+        );
+        assert_eq!(
+            order_by_to_str(OrderBy::Oldest, Direction::Ascending),
+            "latest"
+        );
+        assert_eq!(
+            order_by_to_str(OrderBy::Seeds, Direction::Descending),
+            "seeds"
+        );
+    }
+
+    #[test]
+    fn test_filter_apply_direction() {
+        let filter = |order_by| {
+            Filters::default()
+                .order_by(order_by)
+                .direction(Direction::Ascending)
+                .build()
+        };
+        let names = |filter: Filter| {
+            let mut response = Response {
+                page: Page {
+                    current: 1,
+                    of: 1,
+                    total: 3,
+                    next: None,
+                    previous: None,
+                },
+                movies: ["Heat", "Ronin", "Thief"]
+                    .map(|name| crate::test_util::movie(name, 1995, 7.5, vec![]))
+                    .into(),
+            };
+            filter.apply_to(&mut response);
+            response
+                .movies
+                .into_iter()
+                .map(|movie| movie.name)
+                .collect::<Vec<_>>()
+        };
+
+        for order_by in [OrderBy::Seeds, OrderBy::Peers, OrderBy::Downloads] {
+            assert!(filter(order_by).validate().is_ok());
+            assert_eq!(names(filter(order_by)), ["Thief", "Ronin", "Heat"]);
+        }
+        assert_eq!(names(filter(OrderBy::Latest)), ["Heat", "Ronin", "Thief"]);
+        assert_eq!(
+            names(Filters::default().order_by(OrderBy::Seeds).build()),
+            ["Heat", "Ronin", "Thief"]
+        );
+    }
+
+    #[test]
+    fn test_filter_url_round_trip() {
+        let filters = [
+            Filters::default().build(),
+            Filters::default()
+                .quality(Quality::new(Resolution::P1080).codec(Codec::X265))
+                .genre(Genre::SciFi)
+                .rating(Rating::Seven)
+                .order_by(OrderBy::Seeds)
+                .year(Year::Range2010to2019)
+                .language("fr")
+                .page(4)
+                .build(),
+            Filters::default()
+                .quality(Resolution::ThreeD)
+                .genre(Genre::FilmNoir)
+                .year(Year::Equal(1946))
+                .order_by(OrderBy::Alphabetical)
+                .build(),
+            Filters::default()
+                .year(Year::Range1970to1979)
+                .order_by(OrderBy::Oldest)
+                .build(),
+        ];
+
+        for filter in filters {
+            let url = filter.to_url("https://yts.lt", "the godfather").unwrap();
+            let (query, parsed) = Filter::from_url(&url).unwrap();
+            assert_eq!(query, "the godfather");
+            assert_eq!(parsed, filter);
+            assert_eq!(parsed.to_url("https://yts.lt", &query).unwrap(), url);
+        }
+    }
+
+    #[test]
+    fn test_filter_to_url_unrepresentable() {
+        let ascending = |order_by| {
+            Filters::default()
+                .order_by(order_by)
+                .direction(Direction::Ascending)
+                .build()
+        };
+        let range = Filters::default().year(Year::Range(1971, 1975)).build();
+        let quality = |quality: Quality| Filters::default().quality(quality).build();
+
+        for filter in [
+            ascending(OrderBy::Latest),
+            ascending(OrderBy::Oldest),
+            range,
+            quality(Quality::new(Resolution::P720).source(Source::BluRay)),
+            quality(Quality::new(Resolution::P2160).codec(Codec::X265)),
+            quality(
+                Quality::new(Resolution::P1080)
+                    .codec(Codec::X265)
+                    .source(Source::Web),
+            ),
+        ] {
+            assert!(matches!(
+                filter.to_url("https://yts.lt", "heat"),
+                Err(crate::Error::NoBrowseUrl(_))
+            ));
+        }
+        for year in [Year::Range(1972, 1972), Year::Range(1980, 1989)] {
+            let filter = Filters::default().year(year).build();
+            assert!(filter.to_url("https://yts.lt", "heat").is_err());
+        }
+
+        assert_eq!(
+            ascending(OrderBy::Latest)
+                .browse_url("https://yts.lt", "heat")
+                .unwrap(),
+            "https://yts.lt/browse-movies/heat/all/all/0/oldest/0/all"
+        );
+        assert_eq!(
+            Filters::default()
+                .year(Year::Range(1971, 1975))
+                .build()
+                .browse_url("https://yts.lt", "heat")
+                .unwrap(),
+            "https://yts.lt/browse-movies/heat/all/all/0/latest/1970-1979/all"
+        );
+        assert_eq!(
+            quality(Quality::new(Resolution::P720).source(Source::BluRay))
+                .browse_url("https://yts.lt", "heat")
+                .unwrap(),
+            "https://yts.lt/browse-movies/heat/720p/all/0/latest/0/all"
+        );
+    }
+
+    #[test]
+    fn test_filter_from_url() {
+        let (query, filter) = Filter::from_url(
+            "https://yts.lt/browse-movies/heat/1080p.x265/crime/8/oldest/1990-1999/en?page=2",
+        )
+        .unwrap();
+        assert_eq!(query, "heat");
+        assert_eq!(
+            filter.quality,
+            Some(Quality::new(Resolution::P1080).codec(Codec::X265))
+        );
+        assert_eq!(filter.genre, Genre::Crime);
+        assert_eq!(filter.rating, Rating::Eight);
+        assert_eq!(filter.order_by, OrderBy::Oldest);
+        assert_eq!(filter.year, Year::Range1990to1999);
+        assert_eq!(filter.language.as_deref(), Some("en"));
+        assert_eq!(filter.page, 2);
+
+        let (query, filter) = Filter::from_url("https://yts.lt/browse-movies/").unwrap();
+        assert_eq!(query, "");
+        assert_eq!(filter, Filters::default().build());
+
+        let filter: Filter = "https://yts.lt/browse-movies/0/all/all/0/latest/1971-1975/all"
+            .parse()
+            .unwrap();
+        assert_eq!(filter.year, Year::Range(1971, 1975));
+    }
+
+    #[test]
+    fn test_filter_from_url_errors() {
+        for url in [
+            "not a url",
+            "https://yts.lt/movies/the-godfather-1972",
+            "https://yts.lt/browse-movies/heat/1440p",
+            "https://yts.lt/browse-movies/heat/720p.BluRay",
+            "https://yts.lt/browse-movies/heat/2160p.x265",
+            "https://yts.lt/browse-movies/heat/all/cooking",
+            "https://yts.lt/browse-movies/heat/all/all/10",
+            "https://yts.lt/browse-movies/heat/all/all/0/popularity",
+            "https://yts.lt/browse-movies/heat/all/all/0/latest/1999-1990",
+            "https://yts.lt/browse-movies/heat/all/all/0/latest/0/e+n",
+            "https://yts.lt/browse-movies/heat/all/all/0/latest/0/all/extra",
+            "https://yts.lt/browse-movies/heat?page=two",
+        ] {
+            assert!(Filter::from_url(url).is_err(), "{url}");
+        }
+    }
+}
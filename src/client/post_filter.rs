@@ -0,0 +1,253 @@
+use regex::Regex;
+
+use crate::{Genre, Movie, Response, Torrent};
+
+/// Filters applied on the client side, after results have been fetched.
+///
+/// The browse page only supports a single genre and a minimum rating. A `PostFilter`
+/// narrows results further. Movie criteria (genres, years, title) are checked against
+/// the search results directly, while torrent criteria (seeds, size, runtime) need the
+/// torrents of each movie and are satisfied when at least one torrent meets all of them.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::{Genre, PostFilter};
+///
+/// # fn example() -> yts_movies::Result {
+/// let post_filter = PostFilter::default()
+///     .all_genres([Genre::Crime, Genre::Drama])
+///     .exclude_genres([Genre::Comedy])
+///     .min_seeds(20)
+///     .max_size(4 * 1024 * 1024 * 1024)
+///     .years(1970, 1979)
+///     .title("(?i)^the ")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PostFilter {
+    all_genres: Vec<Genre>,
+    any_genres: Vec<Genre>,
+    exclude_genres: Vec<Genre>,
+    min_year: Option<u32>,
+    max_year: Option<u32>,
+    title: Option<Regex>,
+    min_seeds: Option<u32>,
+    max_size: Option<u64>,
+    min_runtime: Option<u32>,
+    max_runtime: Option<u32>,
+}
+
+impl PostFilter {
+    /// Requires movies to have every one of these genres.
+    pub fn all_genres(mut self, genres: impl IntoIterator<Item = Genre>) -> Self {
+        self.all_genres.extend(genres);
+        self
+    }
+
+    /// Requires movies to have at least one of these genres.
+    pub fn any_genres(mut self, genres: impl IntoIterator<Item = Genre>) -> Self {
+        self.any_genres.extend(genres);
+        self
+    }
+
+    /// Rejects movies having any of these genres.
+    pub fn exclude_genres(mut self, genres: impl IntoIterator<Item = Genre>) -> Self {
+        self.exclude_genres.extend(genres);
+        self
+    }
+
+    /// Requires the release year to be within `from` and `to`, both inclusive.
+    pub fn years(mut self, from: u32, to: u32) -> Self {
+        self.min_year = Some(from);
+        self.max_year = Some(to);
+        self
+    }
+
+    /// Requires the movie title to match a regular expression.
+    ///
+    /// # Errors
+    /// Returns an error if `pattern` is not a valid regular expression.
+    pub fn title(mut self, pattern: &str) -> crate::Result<Self> {
+        self.title = Some(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Requires a torrent with at least this many seeds.
+    pub fn min_seeds(mut self, seeds: u32) -> Self {
+        self.min_seeds = Some(seeds);
+        self
+    }
+
+    /// Requires a torrent no larger than this many bytes.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Requires a runtime within `min` and `max` minutes, both inclusive.
+    pub fn runtime(mut self, min: u32, max: u32) -> Self {
+        self.min_runtime = Some(min);
+        self.max_runtime = Some(max);
+        self
+    }
+
+    /// Returns `true` if any torrent criterion is set, meaning the torrents of each
+    /// movie must be fetched to evaluate the filter.
+    pub fn needs_torrents(&self) -> bool {
+        self.min_seeds.is_some()
+            || self.max_size.is_some()
+            || self.min_runtime.is_some()
+            || self.max_runtime.is_some()
+    }
+
+    /// Returns `true` if the movie meets the genre, year and title criteria.
+    pub fn matches_movie(&self, movie: &Movie) -> bool {
+        self.all_genres
+            .iter()
+            .all(|genre| movie.genres.contains(genre))
+            && (self.any_genres.is_empty()
+                || self
+                    .any_genres
+                    .iter()
+                    .any(|genre| movie.genres.contains(genre)))
+            && !self
+                .exclude_genres
+                .iter()
+                .any(|genre| movie.genres.contains(genre))
+            && self.min_year.is_none_or(|year| movie.year >= year)
+            && self.max_year.is_none_or(|year| movie.year <= year)
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|title| title.is_match(&movie.name))
+    }
+
+    /// Returns `true` if the torrent meets the seeds, size and runtime criteria.
+    ///
+    /// A criterion whose value cannot be read from the torrent is not met.
+    pub fn matches_torrent(&self, torrent: &Torrent) -> bool {
+        let seeds = torrent.seeds();
+        let size = torrent.size_bytes();
+        let runtime = torrent.runtime_minutes();
+
+        self.min_seeds
+            .is_none_or(|min| seeds.is_some_and(|seeds| seeds >= min))
+            && self
+                .max_size
+                .is_none_or(|max| size.is_some_and(|size| size <= max))
+            && self
+                .min_runtime
+                .is_none_or(|min| runtime.is_some_and(|runtime| runtime >= min))
+            && self
+                .max_runtime
+                .is_none_or(|max| runtime.is_some_and(|runtime| runtime <= max))
+    }
+
+    /// Returns `true` if at least one torrent meets the torrent criteria.
+    ///
+    /// Always `true` when no torrent criterion is set.
+    pub fn matches_torrents(&self, torrents: &[Torrent]) -> bool {
+        !self.needs_torrents() || torrents.iter().any(|torrent| self.matches_torrent(torrent))
+    }
+
+    /// Drops the movies of a response that do not meet the movie criteria.
+    ///
+    /// Torrent criteria are not checked here, since they require fetching the torrents
+    /// of each movie.
+    pub fn apply(&self, response: &mut Response) {
+        response.movies.retain(|movie| self.matches_movie(movie));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Genre, Movie, Quality, Resolution, Torrent};
+
+    use super::PostFilter;
+
+    fn movie(name: &str, year: u32, genres: &[Genre]) -> Movie {
+        Movie::new(
+            name.to_string(),
+            year,
+            8.0,
+            genres.to_vec(),
+            String::new(),
+            String::new(),
+        )
+    }
+
+    fn torrent(size: &str, runtime: &str, peers_seeds: &str) -> Torrent {
+        Torrent::new(
+            Quality::new(Resolution::P1080),
+            size,
+            "English",
+            runtime,
+            peers_seeds,
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_post_filter_genres() {
+        let godfather = movie("The Godfather", 1972, &[Genre::Crime, Genre::Drama]);
+        let heat = movie(
+            "Heat",
+            1995,
+            &[Genre::Action, Genre::Crime, Genre::Thriller],
+        );
+
+        let post_filter = PostFilter::default().all_genres([Genre::Crime, Genre::Drama]);
+        assert!(post_filter.matches_movie(&godfather));
+        assert!(!post_filter.matches_movie(&heat));
+
+        let post_filter = PostFilter::default().any_genres([Genre::Drama, Genre::Thriller]);
+        assert!(post_filter.matches_movie(&godfather));
+        assert!(post_filter.matches_movie(&heat));
+
+        let post_filter = PostFilter::default().exclude_genres([Genre::Action]);
+        assert!(post_filter.matches_movie(&godfather));
+        assert!(!post_filter.matches_movie(&heat));
+    }
+
+    #[test]
+    fn test_post_filter_years_and_title() {
+        let godfather = movie("The Godfather", 1972, &[]);
+        let heat = movie("Heat", 1995, &[]);
+
+        let post_filter = PostFilter::default().years(1970, 1979);
+        assert!(post_filter.matches_movie(&godfather));
+        assert!(!post_filter.matches_movie(&heat));
+
+        let post_filter = PostFilter::default().title("(?i)^the ").unwrap();
+        assert!(post_filter.matches_movie(&godfather));
+        assert!(!post_filter.matches_movie(&heat));
+
+        assert!(PostFilter::default().title("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_post_filter_torrents() {
+        let torrents = [
+            torrent("1.2 GB", "2 hr 55 min", "3 / 12"),
+            torrent("3.5 GB", "2 hr 55 min", "40 / 250"),
+        ];
+
+        let post_filter = PostFilter::default();
+        assert!(!post_filter.needs_torrents());
+        assert!(post_filter.matches_torrents(&[]));
+
+        let post_filter = PostFilter::default().min_seeds(100);
+        assert!(post_filter.needs_torrents());
+        assert!(!post_filter.matches_torrent(&torrents[0]));
+        assert!(post_filter.matches_torrents(&torrents));
+
+        let post_filter = PostFilter::default().min_seeds(100).max_size(2 << 30);
+        assert!(!post_filter.matches_torrents(&torrents));
+
+        let post_filter = PostFilter::default().runtime(90, 150);
+        assert!(!post_filter.matches_torrents(&torrents));
+        assert!(!post_filter.matches_torrent(&torrent("1 GB", "unknown", "1 / 1")));
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::{Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Notify;
+
+/// Token-bucket rate limiter for requests sent to the website.
+///
+/// Requests are admitted at `requests_per_second` on average, with up to `burst` requests
+/// allowed back to back. Optionally, the number of requests in flight can be capped, and
+/// consecutive requests spaced by a minimum delay plus a random jitter. Only requests that
+/// reach the network are limited; cache hits and cassette replays are not.
+///
+/// A limiter is safe to share between tasks and threads, and between clients when wrapped
+/// in an [`Arc`](std::sync::Arc). Clones of a client share the same limiter.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yts_movies::{RateLimiter, Yts};
+///
+/// let yts = Yts::default().with_rate_limiter(
+///     RateLimiter::new(2.0, 5)
+///         .max_concurrent(2)
+///         .min_delay(Duration::from_millis(250), Duration::from_millis(500)),
+/// );
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Tokens added per second. Non-positive disables the token bucket.
+    rate: f64,
+    /// Maximum number of tokens stored.
+    burst: f64,
+    /// Maximum number of requests in flight, if capped.
+    max_concurrent: Option<usize>,
+    /// Minimum delay between the start of two requests.
+    min_delay: Duration,
+    /// Upper bound of the random delay added to `min_delay`.
+    jitter: Duration,
+    /// Bucket and in-flight state.
+    state: Mutex<LimiterState>,
+    /// Wakes blocking callers waiting for a request to finish.
+    finished: Condvar,
+    /// Wakes async callers waiting for a request to finish.
+    finished_async: Notify,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    /// Tokens available, negative when requests are already queued.
+    tokens: f64,
+    /// When `tokens` was last refilled.
+    refilled_at: Instant,
+    /// Earliest start of the next request, honouring the minimum delay.
+    next_start: Instant,
+    /// Number of requests in flight.
+    in_flight: usize,
+    /// State of the jitter random number generator.
+    seed: u64,
+}
+
+/// Slot held by a request in flight, released when dropped.
+#[derive(Debug)]
+pub(crate) struct Permit<'a> {
+    limiter: &'a RateLimiter,
+}
+
+impl RateLimiter {
+    /// Creates a limiter.
+    ///
+    /// # Parameters
+    /// - `requests_per_second`: Average rate of requests. A non-positive rate disables the
+    ///   token bucket, leaving only the concurrency and delay limits.
+    /// - `burst`: Number of requests allowed back to back. At least one.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        let now = Instant::now();
+
+        Self {
+            rate: requests_per_second,
+            burst,
+            max_concurrent: None,
+            min_delay: Duration::ZERO,
+            jitter: Duration::ZERO,
+            state: Mutex::new(LimiterState {
+                tokens: burst,
+                refilled_at: now,
+                next_start: now,
+                in_flight: 0,
+                seed: RandomState::new().build_hasher().finish() | 1,
+            }),
+            finished: Condvar::new(),
+            finished_async: Notify::new(),
+        }
+    }
+
+    /// Caps the number of requests in flight. At least one.
+    pub fn max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = Some(max_concurrent.max(1));
+        self
+    }
+
+    /// Spaces the start of consecutive requests by `min_delay` plus a random delay up to
+    /// `jitter`.
+    pub fn min_delay(mut self, min_delay: Duration, jitter: Duration) -> Self {
+        self.min_delay = min_delay;
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the number of requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.lock().in_flight
+    }
+
+    /// Waits, without blocking the thread, until a request may be sent.
+    #[cfg(feature = "async")]
+    pub(crate) async fn acquire(&self) -> Permit<'_> {
+        loop {
+            let finished = self.finished_async.notified();
+            let mut finished = std::pin::pin!(finished);
+            finished.as_mut().enable();
+
+            if self.try_enter() {
+                break;
+            }
+            finished.await;
+        }
+
+        let permit = Permit { limiter: self };
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+
+        permit
+    }
+
+    /// Blocks the thread until a request may be sent.
+    #[cfg(any(test, feature = "blocking"))]
+    pub(crate) fn acquire_blocking(&self) -> Permit<'_> {
+        let mut state = self.lock();
+        while self.is_full(&state) {
+            state = self
+                .finished
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        state.in_flight += 1;
+        drop(state);
+
+        let permit = Permit { limiter: self };
+        std::thread::sleep(self.reserve());
+
+        permit
+    }
+
+    /// Takes an in-flight slot if one is free.
+    #[cfg(feature = "async")]
+    fn try_enter(&self) -> bool {
+        let mut state = self.lock();
+        if self.is_full(&state) {
+            return false;
+        }
+        state.in_flight += 1;
+        true
+    }
+
+    /// Returns `true` if no more requests may be in flight.
+    fn is_full(&self, state: &LimiterState) -> bool {
+        self.max_concurrent
+            .is_some_and(|max| state.in_flight >= max)
+    }
+
+    /// Takes a token and a start slot, returning how long to wait before sending.
+    fn reserve(&self) -> Duration {
+        let mut state = self.lock();
+        let now = Instant::now();
+
+        let mut start = now;
+        if self.rate > 0.0 {
+            let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * self.rate).min(self.burst);
+            state.refilled_at = now;
+
+            state.tokens -= 1.0;
+            if state.tokens < 0.0 {
+                start += Duration::from_secs_f64(-state.tokens / self.rate);
+            }
+        }
+        start = start.max(state.next_start);
+
+        let jitter = if self.jitter.is_zero() {
+            Duration::ZERO
+        } else {
+            self.jitter.mul_f64(next_random(&mut state.seed))
+        };
+        state.next_start = start + self.min_delay + jitter;
+
+        start - now
+    }
+
+    /// Locks the state, recovering it if another thread panicked while holding it.
+    fn lock(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.limiter.lock().in_flight -= 1;
+        self.limiter.finished.notify_one();
+        self.limiter.finished_async.notify_waiters();
+    }
+}
+
+/// Returns a pseudo-random number in `[0, 1)` using xorshift64*.
+fn next_random(seed: &mut u64) -> f64 {
+    *seed ^= *seed >> 12;
+    *seed ^= *seed << 25;
+    *seed ^= *seed >> 27;
+    let value = seed.wrapping_mul(0x2545f4914f6cdd1d);
+
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use super::RateLimiter;
+
+    #[test]
+    fn test_rate_limiter_burst_then_rate() {
+        let limiter = RateLimiter::new(20.0, 3);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            drop(limiter.acquire_blocking());
+        }
+        assert!(start.elapsed() < Duration::from_millis(40));
+
+        for _ in 0..2 {
+            drop(limiter.acquire_blocking());
+        }
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn test_rate_limiter_min_delay() {
+        let limiter = RateLimiter::new(0.0, 1)
+            .min_delay(Duration::from_millis(30), Duration::from_millis(20));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            drop(limiter.acquire_blocking());
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(60));
+        assert!(elapsed < Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_rate_limiter_max_concurrent() {
+        let limiter = Arc::new(RateLimiter::new(0.0, 1).max_concurrent(2));
+
+        let handles = (0..6)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                std::thread::spawn(move || {
+                    let _permit = limiter.acquire_blocking();
+                    let in_flight = limiter.in_flight();
+                    std::thread::sleep(Duration::from_millis(10));
+                    in_flight
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert!(handle.join().unwrap() <= 2);
+        }
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_rate_limiter_async() {
+        let limiter = Arc::new(RateLimiter::new(50.0, 1).max_concurrent(1));
+        let start = Instant::now();
+
+        let tasks = (0..3)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire().await;
+                    assert_eq!(limiter.in_flight(), 1);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(40));
+        assert_eq!(limiter.in_flight(), 0);
+    }
+}
@@ -0,0 +1,476 @@
+use std::{
+    hash::{BuildHasher, Hasher, RandomState},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use crate::{InfoHash, core::bencode};
+
+/// Magic constant opening a UDP tracker connect request, from BEP 15.
+const PROTOCOL_ID: u64 = 0x41727101980;
+
+/// UDP tracker action: connect.
+const CONNECT: u32 = 0;
+
+/// UDP tracker action: scrape.
+const SCRAPE: u32 = 2;
+
+/// UDP tracker action: error, followed by a message.
+const ERROR: u32 = 3;
+
+/// Size of the buffer receiving UDP tracker responses, room for an error message.
+pub(crate) const MAX_PACKET: usize = 2048;
+
+/// Time to wait for a UDP tracker response before sending the request again.
+///
+/// BEP 15 backs off from 15 seconds, longer than a whole scrape is given, so lost
+/// packets are resent at this fixed interval until the scrape times out instead.
+pub(crate) const RETRANSMIT: Duration = Duration::from_millis(500);
+
+/// Bounds of a tracker `scrape`.
+///
+/// By default each tracker has five seconds to answer, and four trackers are asked at
+/// the same time.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yts_movies::ScrapeLimits;
+///
+/// let limits = ScrapeLimits::default()
+///     .timeout(Duration::from_secs(2))
+///     .concurrency(8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrapeLimits {
+    /// Time allowed to each tracker, for the whole exchange.
+    pub(crate) timeout: Duration,
+    /// Maximum number of trackers asked at the same time.
+    pub(crate) concurrency: usize,
+}
+
+/// Swarm counts reported by a tracker for one torrent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwarmHealth {
+    /// Peers with the complete torrent.
+    pub seeders: u32,
+    /// Peers still downloading.
+    pub leechers: u32,
+    /// Number of completed downloads reported to the tracker.
+    pub completed: u32,
+}
+
+/// Result of a tracker `scrape`.
+#[derive(Debug)]
+pub struct Scrape {
+    /// The torrent scraped.
+    pub info_hash: InfoHash,
+    /// Each tracker URL with its answer or error, in the order given.
+    pub trackers: Vec<(String, crate::Result<SwarmHealth>)>,
+}
+
+/// A tracker, by protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Tracker {
+    /// A UDP tracker at `host:port`.
+    Udp(String),
+    /// An HTTP(S) tracker, by its scrape URL.
+    Http(String),
+}
+
+impl Default for ScrapeLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            concurrency: 4,
+        }
+    }
+}
+
+impl ScrapeLimits {
+    /// Gives each tracker at most `timeout` to answer.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Asks at most `concurrency` trackers at the same time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+impl Scrape {
+    /// Collects the answer of each tracker, given with its index in any order.
+    pub(crate) fn collect(
+        info_hash: InfoHash,
+        mut trackers: Vec<(usize, (String, crate::Result<SwarmHealth>))>,
+    ) -> Self {
+        trackers.sort_by_key(|(index, _)| *index);
+
+        Self {
+            info_hash,
+            trackers: trackers.into_iter().map(|(_, tracker)| tracker).collect(),
+        }
+    }
+
+    /// Returns the answer with the most seeders, then leechers, as trackers only see the
+    /// peers announcing to them.
+    pub fn best(&self) -> Option<SwarmHealth> {
+        self.trackers
+            .iter()
+            .filter_map(|(_, health)| health.as_ref().ok())
+            .max_by_key(|health| (health.seeders, health.leechers))
+            .copied()
+    }
+}
+
+impl Tracker {
+    /// Reads a tracker announce URL, e.g. `udp://tracker.example:1337/announce`.
+    ///
+    /// HTTP trackers are scraped at the announce URL with its last path segment renamed
+    /// from `announce` to `scrape`, as described by BEP 48.
+    ///
+    /// # Errors
+    /// Returns [`Error::TrackerError`](crate::Error::TrackerError) for an unknown scheme, a
+    /// UDP URL without port, or an HTTP tracker that does not support scraping.
+    pub(crate) fn parse(url: &str) -> crate::Result<Self> {
+        let parsed =
+            reqwest::Url::parse(url).map_err(|_| crate::Error::ParseError(url.to_string()))?;
+        match parsed.scheme() {
+            "udp" => match (parsed.host_str(), parsed.port()) {
+                (Some(host), Some(port)) => Ok(Tracker::Udp(format!("{host}:{port}"))),
+                _ => Err(error(url, "missing host or port")),
+            },
+            "http" | "https" => {
+                let (base, segment) = parsed
+                    .path()
+                    .rsplit_once('/')
+                    .ok_or_else(|| error(url, "scrape not supported"))?;
+                let rest = segment
+                    .strip_prefix("announce")
+                    .ok_or_else(|| error(url, "scrape not supported"))?;
+
+                let mut scrape = parsed.clone();
+                scrape.set_path(&format!("{base}/scrape{rest}"));
+                Ok(Tracker::Http(scrape.to_string()))
+            }
+            scheme => Err(error(url, &format!("unsupported scheme {scheme}"))),
+        }
+    }
+}
+
+/// Returns the error for a `problem` with the tracker at `url`.
+pub(crate) fn error(url: &str, problem: &str) -> crate::Error {
+    crate::Error::TrackerError(format!("{url}: {problem}"))
+}
+
+/// Returns a random transaction id, matching a UDP response to its request.
+pub(crate) fn transaction_id() -> u32 {
+    RandomState::new().build_hasher().finish() as u32
+}
+
+/// Returns the local address to bind to reach `target`: any port, same IP version.
+pub(crate) fn local_addr(target: &SocketAddr) -> SocketAddr {
+    match target {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    }
+}
+
+/// Returns a UDP connect request.
+pub(crate) fn connect_request(transaction_id: u32) -> [u8; 16] {
+    let mut request = [0; 16];
+    request[..8].copy_from_slice(&PROTOCOL_ID.to_be_bytes());
+    request[8..12].copy_from_slice(&CONNECT.to_be_bytes());
+    request[12..].copy_from_slice(&transaction_id.to_be_bytes());
+    request
+}
+
+/// Reads the connection id from a UDP connect response, or returns `None` if the
+/// response answers another transaction.
+///
+/// # Errors
+/// Returns [`Error::TrackerError`](crate::Error::TrackerError) if the tracker answered an
+/// error or the response is malformed.
+pub(crate) fn connected(
+    url: &str,
+    response: &[u8],
+    transaction_id: u32,
+) -> crate::Result<Option<u64>> {
+    let Some(payload) = payload(url, response, CONNECT, transaction_id)? else {
+        return Ok(None);
+    };
+    let connection_id = payload
+        .first_chunk::<8>()
+        .ok_or_else(|| error(url, "truncated connect response"))?;
+
+    Ok(Some(u64::from_be_bytes(*connection_id)))
+}
+
+/// Returns a UDP scrape request for one torrent.
+pub(crate) fn scrape_request(
+    connection_id: u64,
+    transaction_id: u32,
+    info_hash: &InfoHash,
+) -> [u8; 36] {
+    let mut request = [0; 36];
+    request[..8].copy_from_slice(&connection_id.to_be_bytes());
+    request[8..12].copy_from_slice(&SCRAPE.to_be_bytes());
+    request[12..16].copy_from_slice(&transaction_id.to_be_bytes());
+    request[16..].copy_from_slice(info_hash.as_bytes());
+    request
+}
+
+/// Reads the swarm counts from a UDP scrape response for one torrent, or returns `None`
+/// if the response answers another transaction.
+///
+/// # Errors
+/// Returns [`Error::TrackerError`](crate::Error::TrackerError) if the tracker answered an
+/// error or the response is malformed.
+pub(crate) fn scraped(
+    url: &str,
+    response: &[u8],
+    transaction_id: u32,
+) -> crate::Result<Option<SwarmHealth>> {
+    let Some(payload) = payload(url, response, SCRAPE, transaction_id)? else {
+        return Ok(None);
+    };
+    let counts = payload
+        .first_chunk::<12>()
+        .ok_or_else(|| error(url, "truncated scrape response"))?;
+    let count = |at: usize| {
+        u32::from_be_bytes([counts[at], counts[at + 1], counts[at + 2], counts[at + 3]])
+    };
+
+    Ok(Some(SwarmHealth {
+        seeders: count(0),
+        completed: count(4),
+        leechers: count(8),
+    }))
+}
+
+/// Returns the payload of a UDP response to `action`, after its header, or `None` if the
+/// response answers another transaction, e.g. a late answer to a resent request.
+fn payload<'a>(
+    url: &str,
+    response: &'a [u8],
+    action: u32,
+    transaction_id: u32,
+) -> crate::Result<Option<&'a [u8]>> {
+    let (header, payload) = response
+        .split_at_checked(8)
+        .ok_or_else(|| error(url, "truncated response"))?;
+    let answered = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    let answered_id = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+
+    if answered_id != transaction_id {
+        return Ok(None);
+    }
+    match answered {
+        ERROR => Err(error(url, &String::from_utf8_lossy(payload))),
+        answered if answered == action => Ok(Some(payload)),
+        answered => Err(error(url, &format!("unexpected action {answered}"))),
+    }
+}
+
+/// Returns the HTTP scrape URL for one torrent, with the info hash percent-encoded.
+pub(crate) fn http_url(scrape_url: &str, info_hash: &InfoHash) -> String {
+    let encoded = info_hash
+        .as_bytes()
+        .iter()
+        .map(|&byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect::<String>();
+    let separator = if scrape_url.contains('?') { '&' } else { '?' };
+
+    format!("{scrape_url}{separator}info_hash={encoded}")
+}
+
+/// Reads the swarm counts of one torrent from a bencoded HTTP scrape response.
+///
+/// # Errors
+/// Returns [`Error::TrackerError`](crate::Error::TrackerError) if the tracker answered a
+/// failure or does not list the torrent, or an error if the body is not bencoded.
+pub(crate) fn http_scraped(
+    url: &str,
+    body: &[u8],
+    info_hash: &InfoHash,
+) -> crate::Result<SwarmHealth> {
+    let response = bencode::decode(body)?;
+    if let Some(reason) = response
+        .get("failure reason")
+        .and_then(|reason| reason.bytes())
+    {
+        return Err(error(url, &String::from_utf8_lossy(reason)));
+    }
+
+    let files = response
+        .get("files")
+        .and_then(|files| files.dict())
+        .ok_or_else(|| error(url, "missing files"))?;
+    let (_, file) = files
+        .iter()
+        .find(|(hash, _)| *hash == info_hash.as_bytes())
+        .ok_or_else(|| error(url, "torrent not tracked"))?;
+    let count = |key: &str| {
+        file.get(key)
+            .and_then(|count| count.int())
+            .and_then(|count| u32::try_from(count).ok())
+            .unwrap_or_default()
+    };
+
+    Ok(SwarmHealth {
+        seeders: count("complete"),
+        leechers: count("incomplete"),
+        completed: count("downloaded"),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::InfoHash;
+
+    use super::{
+        Scrape, SwarmHealth, Tracker, connect_request, connected, http_scraped, http_url,
+        scrape_request, scraped,
+    };
+
+    const URL: &str = "udp://tracker.local:1337/announce";
+
+    #[test]
+    fn test_tracker_parse() {
+        assert_eq!(
+            Tracker::parse(URL).unwrap(),
+            Tracker::Udp("tracker.local:1337".to_string())
+        );
+        assert_eq!(
+            Tracker::parse("http://tracker.local/announce.php?passkey=x").unwrap(),
+            Tracker::Http("http://tracker.local/scrape.php?passkey=x".to_string())
+        );
+        assert_eq!(
+            Tracker::parse("https://tracker.local/x/announce").unwrap(),
+            Tracker::Http("https://tracker.local/x/scrape".to_string())
+        );
+        assert!(matches!(
+            Tracker::parse("http://tracker.local/a"),
+            Err(crate::Error::TrackerError(_))
+        ));
+        assert!(matches!(
+            Tracker::parse("udp://tracker.local/announce"),
+            Err(crate::Error::TrackerError(_))
+        ));
+        assert!(matches!(
+            Tracker::parse("wss://tracker.local/announce"),
+            Err(crate::Error::TrackerError(_))
+        ));
+    }
+
+    #[test]
+    fn test_udp_packets() {
+        let info_hash = InfoHash::from([7; 20]);
+
+        let request = connect_request(42);
+        assert_eq!(request[..8], 0x41727101980u64.to_be_bytes());
+        assert_eq!(request[8..], [0, 0, 0, 0, 0, 0, 0, 42]);
+
+        let response = [[0, 0, 0, 0, 0, 0, 0, 42], 99u64.to_be_bytes()].concat();
+        assert_eq!(connected(URL, &response, 42).unwrap(), Some(99));
+        assert_eq!(connected(URL, &response, 43).unwrap(), None);
+        assert!(connected(URL, &response[..12], 42).is_err());
+
+        let request = scrape_request(99, 43, &info_hash);
+        assert_eq!(
+            request[..16],
+            [0, 0, 0, 0, 0, 0, 0, 99, 0, 0, 0, 2, 0, 0, 0, 43]
+        );
+        assert_eq!(request[16..], [7; 20]);
+
+        let response = [0, 0, 0, 2, 0, 0, 0, 43, 0, 0, 0, 12, 0, 0, 1, 0, 0, 0, 0, 3];
+        assert_eq!(
+            scraped(URL, &response, 43).unwrap(),
+            Some(SwarmHealth {
+                seeders: 12,
+                leechers: 3,
+                completed: 256,
+            })
+        );
+        assert_eq!(scraped(URL, &response, 44).unwrap(), None);
+
+        let response = [&[0, 0, 0, 3, 0, 0, 0, 43][..], b"unknown torrent"].concat();
+        assert_eq!(
+            scraped(URL, &response, 43).unwrap_err().to_string(),
+            "Tracker error: udp://tracker.local:1337/announce: unknown torrent"
+        );
+    }
+
+    #[test]
+    fn test_http_scrape() {
+        let info_hash = InfoHash::from(*b"aaaaaaaaaa\x00\xffaaaaaaa~");
+        assert_eq!(
+            http_url("http://tracker.local/scrape", &info_hash),
+            "http://tracker.local/scrape?info_hash=aaaaaaaaaa%00%FFaaaaaaa~"
+        );
+        assert_eq!(
+            http_url("http://tracker.local/scrape?passkey=x", &info_hash),
+            "http://tracker.local/scrape?passkey=x&info_hash=aaaaaaaaaa%00%FFaaaaaaa~"
+        );
+
+        let body = [
+            &b"d5:filesd20:"[..],
+            info_hash.as_bytes(),
+            b"d8:completei12e10:downloadedi256e10:incompletei3eeee",
+        ]
+        .concat();
+        assert_eq!(
+            http_scraped(URL, &body, &info_hash).unwrap(),
+            SwarmHealth {
+                seeders: 12,
+                leechers: 3,
+                completed: 256,
+            }
+        );
+        assert!(matches!(
+            http_scraped(URL, &body, &InfoHash::from([0; 20])),
+            Err(crate::Error::TrackerError(_))
+        ));
+        assert!(matches!(
+            http_scraped(URL, b"d14:failure reason6:bannede", &info_hash),
+            Err(crate::Error::TrackerError(reason)) if reason.ends_with("banned")
+        ));
+    }
+
+    #[test]
+    fn test_scrape_best() {
+        let health = |seeders, leechers| SwarmHealth {
+            seeders,
+            leechers,
+            completed: 0,
+        };
+        let scrape = Scrape::collect(
+            InfoHash::from([0; 20]),
+            vec![
+                (2, ("c".to_string(), Ok(health(5, 9)))),
+                (0, ("a".to_string(), Ok(health(5, 1)))),
+                (1, ("b".to_string(), Err(super::error("b", "timed out")))),
+            ],
+        );
+
+        assert_eq!(
+            scrape
+                .trackers
+                .iter()
+                .map(|(url, _)| url.as_str())
+                .collect::<Vec<_>>(),
+            ["a", "b", "c"]
+        );
+        assert_eq!(scrape.best(), Some(health(5, 9)));
+    }
+}
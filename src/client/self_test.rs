@@ -0,0 +1,37 @@
+use super::Endpoint;
+
+/// Search used by `self_test`, known to return movies with torrents.
+pub(crate) const SELF_TEST_QUERY: &str = "godfather";
+
+/// Report of `self_test`, telling which parsers still work against the live site.
+///
+/// A parser broken by a redesign fails with
+/// [`Error::LayoutChanged`](crate::Error::LayoutChanged), naming the selectors to update.
+#[derive(Debug)]
+pub struct SelfTest {
+    /// Number of movies parsed from a browse page, or the error.
+    pub browse: crate::Result<usize>,
+    /// Number of torrents parsed from the page of the first movie found, or the error.
+    ///
+    /// `None` when the browse page gave no movie to check.
+    pub torrents: Option<crate::Result<usize>>,
+}
+
+impl SelfTest {
+    /// Returns `true` if every parser worked and found data.
+    pub fn is_ok(&self) -> bool {
+        self.broken().is_empty()
+    }
+
+    /// Returns the endpoints whose pages could not be parsed or gave no data.
+    pub fn broken(&self) -> Vec<Endpoint> {
+        let mut broken = Vec::new();
+        if !matches!(self.browse, Ok(movies) if movies > 0) {
+            broken.push(Endpoint::Browse);
+        }
+        if !matches!(self.torrents, Some(Ok(torrents)) if torrents > 0) {
+            broken.push(Endpoint::Movie);
+        }
+        broken
+    }
+}
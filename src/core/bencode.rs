@@ -0,0 +1,256 @@
+/// Deepest nesting of lists and dictionaries accepted, against stack exhaustion.
+const MAX_DEPTH: usize = 64;
+
+/// A decoded bencode value, borrowing from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Value<'a> {
+    /// Encoded bytes of the value, e.g. to hash the `info` dictionary.
+    pub(crate) raw: &'a [u8],
+    /// The decoded value.
+    pub(crate) kind: Kind<'a>,
+}
+
+/// Kind of a bencode value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Kind<'a> {
+    /// An integer, e.g. `i42e`.
+    Int(i64),
+    /// A byte string, e.g. `4:spam`.
+    Bytes(&'a [u8]),
+    /// A list, e.g. `l4:spami42ee`.
+    List(Vec<Value<'a>>),
+    /// A dictionary with keys in sorted order, e.g. `d3:cow3:mooe`.
+    Dict(Vec<(&'a [u8], Value<'a>)>),
+}
+
+impl<'a> Value<'a> {
+    /// Returns the value of `key` if this is a dictionary holding it.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value<'a>> {
+        match &self.kind {
+            Kind::Dict(entries) => entries
+                .iter()
+                .find(|(name, _)| *name == key.as_bytes())
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer, if this is one.
+    pub(crate) fn int(&self) -> Option<i64> {
+        match self.kind {
+            Kind::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte string, if this is one.
+    pub(crate) fn bytes(&self) -> Option<&'a [u8]> {
+        match self.kind {
+            Kind::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte string as text, if this is a valid UTF-8 byte string.
+    pub(crate) fn str(&self) -> Option<&'a str> {
+        self.bytes()
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// Returns the items, if this is a list.
+    pub(crate) fn list(&self) -> Option<&[Value<'a>]> {
+        match &self.kind {
+            Kind::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries in key order, if this is a dictionary.
+    pub(crate) fn dict(&self) -> Option<&[(&'a [u8], Value<'a>)]> {
+        match &self.kind {
+            Kind::Dict(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a dictionary.
+    pub(crate) fn is_dict(&self) -> bool {
+        matches!(self.kind, Kind::Dict(_))
+    }
+}
+
+/// Decodes a complete bencode document.
+///
+/// # Errors
+/// Returns [`Error::InvalidTorrent`](crate::Error::InvalidTorrent) if `bytes` is not
+/// exactly one valid bencode value.
+pub(crate) fn decode(bytes: &[u8]) -> crate::Result<Value<'_>> {
+    let mut decoder = Decoder { bytes, position: 0 };
+    let value = decoder.value(0)?;
+    if decoder.position != bytes.len() {
+        return Err(decoder.error("trailing data"));
+    }
+    Ok(value)
+}
+
+/// Cursor over the bytes being decoded.
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    /// Decodes the value at the cursor, nested `depth` levels deep.
+    fn value(&mut self, depth: usize) -> crate::Result<Value<'a>> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+
+        let start = self.position;
+        let kind = match self.peek()? {
+            b'i' => {
+                self.position += 1;
+                let digits = self.until(b'e')?;
+                Kind::Int(self.integer(digits)?)
+            }
+            b'l' => {
+                self.position += 1;
+                let mut items = Vec::new();
+                while self.peek()? != b'e' {
+                    items.push(self.value(depth + 1)?);
+                }
+                self.position += 1;
+                Kind::List(items)
+            }
+            b'd' => {
+                self.position += 1;
+                let mut entries: Vec<(&[u8], Value)> = Vec::new();
+                while self.peek()? != b'e' {
+                    let key = self.string()?;
+                    if entries.last().is_some_and(|(last, _)| *last >= key) {
+                        return Err(self.error("dictionary keys not sorted"));
+                    }
+                    entries.push((key, self.value(depth + 1)?));
+                }
+                self.position += 1;
+                Kind::Dict(entries)
+            }
+            b'0'..=b'9' => Kind::Bytes(self.string()?),
+            _ => return Err(self.error("unexpected byte")),
+        };
+
+        Ok(Value {
+            raw: &self.bytes[start..self.position],
+            kind,
+        })
+    }
+
+    /// Decodes the byte string at the cursor.
+    fn string(&mut self) -> crate::Result<&'a [u8]> {
+        let digits = self.until(b':')?;
+        let length = usize::try_from(self.integer(digits)?)
+            .map_err(|_| self.error("negative string length"))?;
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| self.error("string past the end"))?;
+
+        let value = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(value)
+    }
+
+    /// Returns the bytes up to `delimiter`, moving the cursor past it.
+    fn until(&mut self, delimiter: u8) -> crate::Result<&'a [u8]> {
+        let length = self.bytes[self.position..]
+            .iter()
+            .position(|&byte| byte == delimiter)
+            .ok_or_else(|| self.error("unterminated value"))?;
+
+        let value = &self.bytes[self.position..self.position + length];
+        self.position += length + 1;
+        Ok(value)
+    }
+
+    /// Parses the digits of an integer, rejecting leading zeros and `-0`.
+    fn integer(&self, digits: &[u8]) -> crate::Result<i64> {
+        let canonical = match digits {
+            [b'0'] => true,
+            [b'-', b'1'..=b'9', rest @ ..] | [b'1'..=b'9', rest @ ..] => {
+                rest.iter().all(u8::is_ascii_digit)
+            }
+            _ => false,
+        };
+
+        std::str::from_utf8(digits)
+            .ok()
+            .filter(|_| canonical)
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| self.error("invalid integer"))
+    }
+
+    /// Returns the byte at the cursor.
+    fn peek(&self) -> crate::Result<u8> {
+        self.bytes
+            .get(self.position)
+            .copied()
+            .ok_or_else(|| self.error("unexpected end"))
+    }
+
+    /// Returns the error for a `problem` at the cursor.
+    fn error(&self, problem: &str) -> crate::Error {
+        crate::Error::InvalidTorrent(format!("{problem} at byte {}", self.position))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Kind, decode};
+
+    #[test]
+    fn test_decode() {
+        let value = decode(b"d3:cow3:moo4:listli-42ei0ee4:spam4:eggse").unwrap();
+
+        assert_eq!(value.get("cow").unwrap().str(), Some("moo"));
+        assert_eq!(value.get("spam").unwrap().bytes(), Some(&b"eggs"[..]));
+        let list = value.get("list").unwrap();
+        assert_eq!(list.raw, b"li-42ei0ee");
+        assert_eq!(list.list().unwrap()[0].int(), Some(-42));
+        assert_eq!(value.dict().unwrap()[0].0, b"cow");
+        assert!(value.is_dict());
+        assert!(matches!(decode(b"0:").unwrap().kind, Kind::Bytes(b"")));
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        for invalid in [
+            &b""[..],
+            b"i42",
+            b"i-0e",
+            b"i042e",
+            b"ie",
+            b"5:spam",
+            b"l4:spam",
+            b"d4:spam3:cow3:cow3:mooe",
+            b"i1ei2e",
+            b"x",
+        ] {
+            assert!(
+                matches!(decode(invalid), Err(crate::Error::InvalidTorrent(_))),
+                "{}",
+                String::from_utf8_lossy(invalid)
+            );
+        }
+        assert!(
+            decode(
+                &[b'l'; 100]
+                    .iter()
+                    .chain(&[b'e'; 100])
+                    .copied()
+                    .collect::<Vec<_>>()
+            )
+            .is_err()
+        );
+    }
+}
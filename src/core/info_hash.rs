@@ -0,0 +1,172 @@
+use std::str::FromStr;
+
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+/// BitTorrent v1 info hash: the SHA-1 digest of the bencoded `info` dictionary of a
+/// torrent.
+///
+/// Displayed and parsed as 40 hexadecimal digits, as in magnet links.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::InfoHash;
+///
+/// let hash: InfoHash = "A1B2C3D4E5F60718293A4B5C6D7E8F9012345678".parse()?;
+/// assert_eq!(hash.to_string(), "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678");
+/// # Ok::<(), yts_movies::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InfoHash([u8; 20]);
+
+impl InfoHash {
+    /// Returns the info hash of the bencoded `info` dictionary.
+    pub(crate) fn of(info: &[u8]) -> Self {
+        Self(Sha1::digest(info).into())
+    }
+
+    /// Returns the 20 bytes of the hash.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+}
+
+impl From<[u8; 20]> for InfoHash {
+    fn from(value: [u8; 20]) -> Self {
+        Self(value)
+    }
+}
+
+impl std::fmt::Display for InfoHash {
+    /// Formats the hash as 40 lowercase hexadecimal digits.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+impl FromStr for InfoHash {
+    type Err = crate::Error;
+
+    /// Parses 40 hexadecimal digits, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hex(s).map(Self)
+    }
+}
+
+/// BitTorrent v2 info hash: the SHA-256 digest of the bencoded `info` dictionary of a
+/// torrent, as defined by BEP 52.
+///
+/// Displayed and parsed as 64 hexadecimal digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InfoHashV2([u8; 32]);
+
+impl InfoHashV2 {
+    /// Returns the v2 info hash of the bencoded `info` dictionary.
+    pub(crate) fn of(info: &[u8]) -> Self {
+        Self(Sha256::digest(info).into())
+    }
+
+    /// Returns the 32 bytes of the hash.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns the hash truncated to 20 bytes, which v2 torrents use with trackers and
+    /// peers expecting a v1 info hash.
+    pub fn truncated(&self) -> InfoHash {
+        let mut hash = [0; 20];
+        hash.copy_from_slice(&self.0[..20]);
+        InfoHash(hash)
+    }
+}
+
+impl From<[u8; 32]> for InfoHashV2 {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl std::fmt::Display for InfoHashV2 {
+    /// Formats the hash as 64 lowercase hexadecimal digits.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+impl FromStr for InfoHashV2 {
+    type Err = crate::Error;
+
+    /// Parses 64 hexadecimal digits, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hex(s).map(Self)
+    }
+}
+
+/// Writes `bytes` as lowercase hexadecimal digits.
+fn write_hex(f: &mut std::fmt::Formatter, bytes: &[u8]) -> std::fmt::Result {
+    bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+}
+
+/// Parses exactly `2 * N` hexadecimal digits, in any case.
+fn parse_hex<const N: usize>(s: &str) -> crate::Result<[u8; N]> {
+    let invalid = || crate::Error::InvalidTorrent(format!("invalid info hash {s}"));
+    if s.len() != 2 * N || !s.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let mut hash = [0; N];
+    for (byte, hex) in hash.iter_mut().zip(s.as_bytes().chunks_exact(2)) {
+        let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(hex, 16).map_err(|_| invalid())?;
+    }
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InfoHash, InfoHashV2};
+
+    #[test]
+    fn test_info_hash() {
+        assert_eq!(
+            InfoHash::of(b"abc").to_string(),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            "A9993E364706816ABA3E25717850C26C9CD0D89D"
+                .parse::<InfoHash>()
+                .unwrap(),
+            InfoHash::of(b"abc")
+        );
+        assert!("a9993e36".parse::<InfoHash>().is_err());
+        assert!(
+            "g9993e364706816aba3e25717850c26c9cd0d89d"
+                .parse::<InfoHash>()
+                .is_err()
+        );
+        assert!(
+            "+9993e364706816aba3e25717850c26c9cd0d89d"
+                .parse::<InfoHash>()
+                .is_err()
+        );
+    }
+    #[test]
+    fn test_info_hash_v2() {
+        let hash = InfoHashV2::of(b"abc");
+        assert_eq!(
+            hash.to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hash.truncated().to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a3"
+        );
+        assert_eq!(hash.to_string().parse::<InfoHashV2>().unwrap(), hash);
+        assert!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a3"
+                .parse::<InfoHashV2>()
+                .is_err()
+        );
+    }
+}
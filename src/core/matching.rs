@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::{Movie, Year};
+
+/// Lowest confidence accepted as a match.
+const MIN_CONFIDENCE: f32 = 0.75;
+
+/// Largest difference, in years, between the requested and the listed release year.
+///
+/// Release years often differ by one between sources, e.g. festival and theatrical dates.
+const YEAR_TOLERANCE: u32 = 1;
+
+/// Confidence factor applied to each year of difference.
+const YEAR_PENALTY: f32 = 0.9;
+
+/// Articles dropped from titles before comparing them.
+const ARTICLES: [&str; 3] = ["the", "a", "an"];
+
+/// A movie found by `find_best_match`, with the confidence of the match.
+#[derive(Debug)]
+pub struct Match {
+    /// The best matching movie.
+    pub movie: Movie,
+    /// Confidence of the match, from 0 to 1. An identical normalized title with the same
+    /// year scores 1.
+    pub confidence: f32,
+}
+
+impl Match {
+    /// Returns the year filter covering the tolerance around `year`, within the years the
+    /// site accepts.
+    pub(crate) fn years(year: u32) -> Year {
+        let to = (year + YEAR_TOLERANCE).min(Year::current());
+        let from = year.saturating_sub(YEAR_TOLERANCE).max(Year::MIN).min(to);
+
+        Year::Range(from, to)
+    }
+
+    /// Returns the candidate best matching `title` and `year`, if confident enough.
+    pub(crate) fn best(title: &str, year: u32, candidates: Vec<Movie>) -> Option<Self> {
+        let title = normalize(title);
+
+        candidates
+            .into_iter()
+            .filter_map(|movie| {
+                let confidence = confidence(&title, year, &movie)?;
+                Some(Self { movie, confidence })
+            })
+            .filter(|candidate| candidate.confidence >= MIN_CONFIDENCE)
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    }
+}
+
+/// Scores `movie` against a normalized `title` and `year`, or `None` if the years are too
+/// far apart.
+fn confidence(title: &str, year: u32, movie: &Movie) -> Option<f32> {
+    let distance = year.abs_diff(movie.year);
+    if distance > YEAR_TOLERANCE {
+        return None;
+    }
+
+    Some(similarity(title, &normalize(&movie.name)) * YEAR_PENALTY.powi(distance as i32))
+}
+
+/// Normalizes a title for comparison: lowercase, without accents, punctuation or
+/// articles, and with roman numerals as numbers.
+///
+/// `The Godfather: Part II` and `Godfather Part 2` both become `godfather part 2`.
+fn normalize(title: &str) -> String {
+    let mut folded = String::with_capacity(title.len());
+    for c in title.chars().flat_map(char::to_lowercase) {
+        match c {
+            '&' => folded.push_str(" and "),
+            '\'' | '’' => {}
+            c if c.is_alphanumeric() => match fold_accent(c) {
+                Some(unaccented) => folded.push_str(unaccented),
+                None => folded.push(c),
+            },
+            _ => folded.push(' '),
+        }
+    }
+
+    folded
+        .split_whitespace()
+        .filter(|token| !ARTICLES.contains(token))
+        .map(|token| roman(token).map_or_else(|| token.to_string(), |n| n.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the unaccented form of a lowercase letter, e.g. `e` for `é`, or `None` if the
+/// letter has no accent.
+fn fold_accent(c: char) -> Option<&'static str> {
+    let unaccented = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'ł' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'ř' => "r",
+        'ś' | 'ş' | 'š' => "s",
+        'ť' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        _ => return None,
+    };
+
+    Some(unaccented)
+}
+
+/// Returns the value of a roman numeral up to 39, as used for sequels, e.g. 2 for `ii`.
+fn roman(token: &str) -> Option<u32> {
+    if token.is_empty() || !token.chars().all(|c| matches!(c, 'i' | 'v' | 'x')) {
+        return None;
+    }
+
+    (1..40).find(|&n| to_roman(n) == token)
+}
+
+/// Returns the lowercase roman numeral of `n`, for `n` below 40.
+fn to_roman(n: u32) -> String {
+    const UNITS: [&str; 10] = ["", "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"];
+    "x".repeat((n / 10) as usize) + UNITS[(n % 10) as usize]
+}
+
+/// Returns the Sørensen–Dice coefficient of the character pairs of two strings.
+fn similarity(a: &str, b: &str) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+
+    let pairs = |value: &str| {
+        let chars = value.chars().collect::<Vec<_>>();
+        chars
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (pairs(a), pairs(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = HashMap::new();
+    for pair in &a {
+        *counts.entry(pair).or_insert(0) += 1;
+    }
+    let shared = b
+        .iter()
+        .filter(|pair| match counts.get_mut(pair) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+        .count();
+
+    (2 * shared) as f32 / (a.len() + b.len()) as f32
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Movie, Year};
+
+    use super::{Match, normalize, similarity};
+
+    fn movie(name: &str, year: u32) -> Movie {
+        Movie::new(
+            name.to_string(),
+            year,
+            8.0,
+            vec![],
+            String::new(),
+            format!("https://yts.lt/movies/{year}"),
+        )
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("The Godfather: Part II"), "godfather part 2");
+        assert_eq!(normalize("Godfather Part 2"), "godfather part 2");
+        assert_eq!(normalize("Amélie"), "amelie");
+        assert_eq!(normalize("Léon: The Professional"), "leon professional");
+        assert_eq!(normalize("Fast & Furious"), "fast and furious");
+        assert_eq!(normalize("Schindler's List"), "schindlers list");
+        assert_eq!(normalize("Rocky IV"), "rocky 4");
+        assert_eq!(normalize("Mix Tape"), "mix tape");
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("godfather", "godfather"), 1.0);
+        assert!(similarity("godfather part 2", "godfather part 3") > 0.75);
+        assert!(similarity("godfather", "goodfellas") < 0.5);
+        assert_eq!(similarity("a", "b"), 0.0);
+    }
+
+    #[test]
+    fn test_years() {
+        let current = Year::current();
+        assert_eq!(Match::years(1974), Year::Range(1973, 1975));
+        assert_eq!(Match::years(1900), Year::Range(1900, 1901));
+        assert_eq!(Match::years(current), Year::Range(current - 1, current));
+        assert_eq!(Match::years(current + 5), Year::Range(current, current));
+        assert!(Match::years(current).validate().is_ok());
+    }
+
+    #[test]
+    fn test_best_match() {
+        let candidates = || {
+            vec![
+                movie("The Godfather", 1972),
+                movie("The Godfather Part II", 1974),
+                movie("The Godfather Part III", 1990),
+                movie("Goodfellas", 1974),
+            ]
+        };
+
+        let found = Match::best("Godfather Part II", 1974, candidates()).unwrap();
+        assert_eq!(found.movie.name, "The Godfather Part II");
+        assert_eq!(found.confidence, 1.0);
+
+        let found = Match::best("Godfather Part 2", 1975, candidates()).unwrap();
+        assert_eq!(found.movie.year, 1974);
+        assert!(found.confidence < 1.0);
+
+        assert!(Match::best("Godfather Part II", 1980, candidates()).is_none());
+        assert!(Match::best("Casablanca", 1974, candidates()).is_none());
+    }
+}
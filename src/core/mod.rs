@@ -1,4 +1,15 @@
+pub(crate) mod bencode;
+mod info_hash;
+mod matching;
 pub mod model;
+mod policy;
 mod response;
+mod selectors;
+mod torrent_file;
 
+pub use info_hash::{InfoHash, InfoHashV2};
+pub use matching::Match;
+pub use policy::{Rejected, Rejection, Selection, TorrentPolicy};
 pub use response::{Page, Response, Torrent};
+pub use selectors::{SelectorProfile, Selectors};
+pub use torrent_file::{FileEntry, Mismatch, TorrentFile};
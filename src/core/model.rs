@@ -9,6 +9,7 @@
 /// - `link`: URL to more information about the movie.
 ///   This field is visible only within the current crate.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Movie {
     /// The title of the movie.
     pub name: String,
@@ -63,7 +64,7 @@ impl Movie {
 /// Represents the genre of a movie.
 ///
 /// This enum covers a wide range of genres, including common and niche categories.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Genre {
     All,
     Action,
@@ -94,6 +95,58 @@ pub enum Genre {
     Western,
 }
 
+impl Genre {
+    /// Every genre, in the order listed by the website.
+    pub const VARIANTS: [Genre; 27] = [
+        Genre::All,
+        Genre::Action,
+        Genre::Adventure,
+        Genre::Animation,
+        Genre::Biography,
+        Genre::Comedy,
+        Genre::Crime,
+        Genre::Documentary,
+        Genre::Drama,
+        Genre::Family,
+        Genre::Fantasy,
+        Genre::FilmNoir,
+        Genre::GameShow,
+        Genre::History,
+        Genre::Horror,
+        Genre::Music,
+        Genre::Musical,
+        Genre::Mystery,
+        Genre::News,
+        Genre::RealityTV,
+        Genre::Romance,
+        Genre::SciFi,
+        Genre::Sport,
+        Genre::TalkShow,
+        Genre::Thriller,
+        Genre::War,
+        Genre::Western,
+    ];
+
+    /// Converts a lowercase URL slug (e.g. `sci-fi`) back into a `Genre`.
+    ///
+    /// Returns `None` if the slug does not match any genre.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yts_movies::Genre;
+    ///
+    /// assert_eq!(Genre::from_slug("film-noir"), Some(Genre::FilmNoir));
+    /// assert_eq!(Genre::from_slug("Film-Noir"), None);
+    /// ```
+    pub fn from_slug(slug: &str) -> Option<Genre> {
+        Self::VARIANTS.into_iter().find(|genre| {
+            let value: &str = genre.into();
+            value == slug
+        })
+    }
+}
+
 impl From<&Genre> for &str {
     /// Converts a reference to a `Genre` enum variant into its corresponding lowercase string representation.
     ///
@@ -0,0 +1,352 @@
+use crate::{Codec, Quality, Resolution, Source, Torrent};
+
+/// Rules picking the best torrent of a movie.
+///
+/// A torrent is a candidate when it meets every requirement: a preferred quality (any
+/// quality when none is listed), no excluded resolution, enough seeds, a size within
+/// bounds and the required language. Candidates are ranked by the order of the quality
+/// preferences, then of the codec and source preferences, then by seeds.
+///
+/// # Examples
+///
+/// Prefer 1080p x265 when well seeded, else any 1080p, never 3D, at most 4 GB:
+///
+/// ```
+/// use yts_movies::{Codec, Quality, Resolution, TorrentPolicy};
+///
+/// let policy = TorrentPolicy::default()
+///     .prefer_if_seeded(Quality::new(Resolution::P1080).codec(Codec::X265), 50)
+///     .prefer(Quality::new(Resolution::P1080))
+///     .exclude(Resolution::ThreeD)
+///     .min_seeds(5)
+///     .max_size(4 * 1024 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TorrentPolicy {
+    qualities: Vec<(Quality, Option<u32>)>,
+    excluded: Vec<Resolution>,
+    min_seeds: Option<u32>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    codecs: Vec<Codec>,
+    sources: Vec<Source>,
+    language: Option<String>,
+}
+
+/// Outcome of [`Torrent::select_best`].
+#[derive(Debug, Clone)]
+pub struct Selection {
+    /// The best candidate, if any torrent met the policy.
+    pub best: Option<Torrent>,
+    /// Torrents not meeting the policy, with the reasons why.
+    pub rejected: Vec<Rejected>,
+}
+
+/// A torrent rejected by a [`TorrentPolicy`].
+#[derive(Debug, Clone)]
+pub struct Rejected {
+    /// The rejected torrent.
+    pub torrent: Torrent,
+    /// Every requirement the torrent does not meet.
+    pub reasons: Vec<Rejection>,
+}
+
+/// Requirement of a [`TorrentPolicy`] a torrent does not meet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The quality is not among the preferences.
+    NotPreferred(Quality),
+    /// The resolution is excluded.
+    Excluded(Resolution),
+    /// Fewer seeds than required, or an unreadable seeds count.
+    TooFewSeeds(Option<u32>),
+    /// A size outside the bounds, or an unreadable size.
+    SizeOutOfBounds(Option<u64>),
+    /// Another language than the required one.
+    Language(String),
+}
+
+impl TorrentPolicy {
+    /// Adds a quality preference, after those already added.
+    ///
+    /// A preference without codec or source accepts any, e.g. `1080p` covers `1080p.x265`.
+    pub fn prefer(mut self, quality: impl Into<Quality>) -> Self {
+        self.qualities.push((quality.into(), None));
+        self
+    }
+
+    /// Adds a quality preference only met by torrents with at least `seeds` seeds.
+    pub fn prefer_if_seeded(mut self, quality: impl Into<Quality>, seeds: u32) -> Self {
+        self.qualities.push((quality.into(), Some(seeds)));
+        self
+    }
+
+    /// Rejects torrents with this resolution.
+    pub fn exclude(mut self, resolution: Resolution) -> Self {
+        self.excluded.push(resolution);
+        self
+    }
+
+    /// Requires at least this many seeds.
+    pub fn min_seeds(mut self, seeds: u32) -> Self {
+        self.min_seeds = Some(seeds);
+        self
+    }
+
+    /// Requires a torrent of at least this many bytes.
+    pub fn min_size(mut self, bytes: u64) -> Self {
+        self.min_size = Some(bytes);
+        self
+    }
+
+    /// Requires a torrent of at most this many bytes.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Ranks codecs in this order, before seeds. Unlisted codecs come last.
+    pub fn prefer_codecs(mut self, codecs: impl IntoIterator<Item = Codec>) -> Self {
+        self.codecs = codecs.into_iter().collect();
+        self
+    }
+
+    /// Ranks sources in this order, before seeds. Unlisted sources come last.
+    pub fn prefer_sources(mut self, sources: impl IntoIterator<Item = Source>) -> Self {
+        self.sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Requires the audio language to contain `language`, ignoring case, e.g. `English`.
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_lowercase());
+        self
+    }
+
+    /// Returns the rank of the first preference met by `torrent`, or the reasons it is
+    /// rejected.
+    fn rank(&self, torrent: &Torrent) -> Result<usize, Vec<Rejection>> {
+        let quality = torrent.quality;
+        let seeds = torrent.seeds();
+        let size = torrent.size_bytes();
+        let mut reasons = Vec::new();
+
+        let preference = if self.qualities.is_empty() {
+            Some(0)
+        } else {
+            self.qualities.iter().position(|(preferred, min_seeds)| {
+                covers(preferred, &quality)
+                    && min_seeds.is_none_or(|min| seeds.is_some_and(|seeds| seeds >= min))
+            })
+        };
+        if preference.is_none() {
+            reasons.push(Rejection::NotPreferred(quality));
+        }
+        if self.excluded.contains(&quality.resolution) {
+            reasons.push(Rejection::Excluded(quality.resolution));
+        }
+        if self
+            .min_seeds
+            .is_some_and(|min| seeds.is_none_or(|seeds| seeds < min))
+        {
+            reasons.push(Rejection::TooFewSeeds(seeds));
+        }
+        if (self.min_size.is_some() || self.max_size.is_some())
+            && size.is_none_or(|size| {
+                self.min_size.is_some_and(|min| size < min)
+                    || self.max_size.is_some_and(|max| size > max)
+            })
+        {
+            reasons.push(Rejection::SizeOutOfBounds(size));
+        }
+        if let Some(language) = &self.language
+            && !torrent.language.to_lowercase().contains(language.as_str())
+        {
+            reasons.push(Rejection::Language(torrent.language.clone()));
+        }
+
+        match preference {
+            Some(preference) if reasons.is_empty() => Ok(preference),
+            _ => Err(reasons),
+        }
+    }
+}
+
+/// Returns `true` if the `preferred` quality covers `quality`, ignoring a missing codec
+/// or source in the preference.
+fn covers(preferred: &Quality, quality: &Quality) -> bool {
+    preferred.resolution == quality.resolution
+        && preferred
+            .codec
+            .is_none_or(|codec| quality.codec == Some(codec))
+        && preferred
+            .source
+            .is_none_or(|source| quality.source == Some(source))
+}
+
+/// Returns the position of `value` in `preferences`, or their count if it is missing.
+fn position<T: PartialEq>(preferences: &[T], value: Option<T>) -> usize {
+    value
+        .and_then(|value| preferences.iter().position(|preferred| *preferred == value))
+        .unwrap_or(preferences.len())
+}
+
+impl Torrent {
+    /// Picks the best of `torrents` according to `policy`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(yts: yts_movies::Yts, movie: yts_movies::Movie) -> yts_movies::Result {
+    /// use yts_movies::{Resolution, Torrent, TorrentPolicy};
+    ///
+    /// let torrents = yts.torrents(&movie).await?;
+    /// let policy = TorrentPolicy::default().prefer(Resolution::P1080).min_seeds(10);
+    /// let selection = Torrent::select_best(&torrents, &policy);
+    /// for rejected in &selection.rejected {
+    ///     println!("{}: {:?}", rejected.torrent.quality, rejected.reasons);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_best(torrents: &[Torrent], policy: &TorrentPolicy) -> Selection {
+        let mut best: Option<(_, &Torrent)> = None;
+        let mut rejected = Vec::new();
+
+        for torrent in torrents {
+            match policy.rank(torrent) {
+                Ok(preference) => {
+                    let key = (
+                        preference,
+                        position(&policy.codecs, torrent.quality.codec),
+                        position(&policy.sources, torrent.quality.source),
+                        std::cmp::Reverse(torrent.seeds().unwrap_or_default()),
+                    );
+                    if best.as_ref().is_none_or(|(best, _)| key < *best) {
+                        best = Some((key, torrent));
+                    }
+                }
+                Err(reasons) => rejected.push(Rejected {
+                    torrent: torrent.clone(),
+                    reasons,
+                }),
+            }
+        }
+
+        Selection {
+            best: best.map(|(_, torrent)| torrent.clone()),
+            rejected,
+        }
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    /// Explains the rejection, e.g. `too few seeds (3)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Rejection::NotPreferred(quality) => write!(f, "quality {quality} not preferred"),
+            Rejection::Excluded(resolution) => {
+                let resolution: &str = resolution.into();
+                write!(f, "resolution {resolution} excluded")
+            }
+            Rejection::TooFewSeeds(Some(seeds)) => write!(f, "too few seeds ({seeds})"),
+            Rejection::TooFewSeeds(None) => write!(f, "unknown seeds"),
+            Rejection::SizeOutOfBounds(Some(size)) => {
+                write!(f, "size out of bounds ({size} bytes)")
+            }
+            Rejection::SizeOutOfBounds(None) => write!(f, "unknown size"),
+            Rejection::Language(language) => write!(f, "language {language} not wanted"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Codec, Quality, Resolution, Source, Torrent};
+
+    use super::{Rejection, TorrentPolicy};
+
+    fn torrent(quality: Quality, size: &str, language: &str, seeds: u32) -> Torrent {
+        Torrent::new(
+            quality,
+            size,
+            language,
+            "2 hr 55 min",
+            &format!("1 / {seeds}"),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_select_best_preferences() {
+        let x265 = Quality::new(Resolution::P1080).codec(Codec::X265);
+        let torrents = [
+            torrent(Quality::new(Resolution::P720), "1.1 GB", "English", 300),
+            torrent(Quality::new(Resolution::P1080), "2.2 GB", "English", 120),
+            torrent(x265, "1.8 GB", "English", 30),
+            torrent(Quality::new(Resolution::ThreeD), "2.1 GB", "English", 8),
+        ];
+        let policy = |seeds| {
+            TorrentPolicy::default()
+                .prefer_if_seeded(x265, seeds)
+                .prefer(Resolution::P1080)
+                .exclude(Resolution::ThreeD)
+        };
+
+        let selection = Torrent::select_best(&torrents, &policy(20));
+        assert_eq!(selection.best.unwrap().quality, x265);
+        assert_eq!(selection.rejected.len(), 2);
+        assert_eq!(
+            selection.rejected[1].reasons,
+            [
+                Rejection::NotPreferred(Quality::new(Resolution::ThreeD)),
+                Rejection::Excluded(Resolution::ThreeD)
+            ]
+        );
+
+        let selection = Torrent::select_best(&torrents, &policy(50));
+        assert_eq!(
+            selection.best.unwrap().quality,
+            Quality::new(Resolution::P1080)
+        );
+
+        let selection = Torrent::select_best(&torrents, &TorrentPolicy::default());
+        assert_eq!(selection.best.unwrap().seeds(), Some(300));
+    }
+
+    #[test]
+    fn test_select_best_requirements() {
+        let web = Quality::new(Resolution::P1080).source(Source::Web);
+        let bluray = Quality::new(Resolution::P1080).source(Source::BluRay);
+        let torrents = [
+            torrent(web, "1.6 GB", "English 5.1", 90),
+            torrent(bluray, "2.4 GB", "English 5.1", 40),
+            torrent(bluray, "5.2 GB", "English 5.1", 400),
+            torrent(bluray, "2.0 GB", "Italian", 400),
+        ];
+        let policy = TorrentPolicy::default()
+            .min_seeds(10)
+            .max_size(3 << 30)
+            .prefer_sources([Source::BluRay])
+            .language("english");
+
+        let selection = Torrent::select_best(&torrents, &policy);
+        assert_eq!(selection.best.unwrap().size, "2.4 GB");
+        assert_eq!(
+            selection.rejected[0].reasons,
+            [Rejection::SizeOutOfBounds(Some(5_583_457_485))]
+        );
+        assert_eq!(
+            selection.rejected[1].reasons[0].to_string(),
+            "language Italian not wanted"
+        );
+
+        let selection = Torrent::select_best(&torrents, &policy.min_seeds(1000));
+        assert!(selection.best.is_none());
+        assert_eq!(selection.rejected.len(), 4);
+        assert_eq!(
+            selection.rejected[0].reasons[0].to_string(),
+            "too few seeds (90)"
+        );
+    }
+}
@@ -1,11 +1,17 @@
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html};
 
-use crate::{Genre, Quality};
+use crate::{Filter, Genre, Quality, SelectorProfile, trace};
 
 use super::model;
 
+/// Number of movies on a full browse page.
+const MOVIES_PER_PAGE: u32 = 20;
+
 /// Represents pagination information for a movie list page.
+///
+/// Read from the pagination widget of the page when it has one.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Page {
     /// The current page number.
     pub current: u32,
@@ -13,28 +19,133 @@ pub struct Page {
     pub of: u32,
     /// The total number of movies available.
     pub total: u32,
+    /// The next page number, if any.
+    pub next: Option<u32>,
+    /// The previous page number, if any.
+    pub previous: Option<u32>,
+}
+
+/// Page numbers read from the pagination widget of a browse page.
+#[derive(Debug, Default)]
+struct Pagination {
+    /// Page marked as current.
+    current: Option<u32>,
+    /// Last page, from the `Last` link or the highest page listed.
+    last: Option<u32>,
+    /// Target of the `Next` link.
+    next: Option<u32>,
+    /// Target of the `Previous` link.
+    previous: Option<u32>,
 }
 
 impl Page {
-    /// Creates a new `Page` instance calculating total pages based on total items.
+    /// Creates a new `Page` instance from the pagination widget of the page.
     ///
     /// # Parameters
-    /// - `current`: The current page number.
+    /// - `requested`: The page number requested.
     /// - `total`: The total number of movies available.
+    /// - `pagination`: The pagination widget, if the page has one.
     ///
     /// # Returns
-    /// A `Page` struct with calculated total pages (`of`).
+    /// A `Page` struct. Without a widget, the site shows all results on one page, but
+    /// the numbers are still derived from `total` in case the widget was not found.
+    fn create(requested: u32, total: u32, pagination: Option<Pagination>) -> Self {
+        let Some(pagination) = pagination else {
+            let of = total.div_ceil(MOVIES_PER_PAGE).max(1);
+            return Self {
+                current: requested,
+                of,
+                total,
+                next: (requested < of).then_some(requested + 1),
+                previous: (requested > 1).then(|| requested - 1),
+            };
+        };
+
+        let current = pagination.current.unwrap_or(requested);
+        Self {
+            current,
+            of: pagination.last.unwrap_or(current).max(current),
+            total,
+            next: pagination.next,
+            previous: pagination.previous,
+        }
+    }
+
+    /// Returns `true` if the site links to a next page.
+    pub fn has_next(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Returns `filter` moved to the next page, or `None` on the last page.
     ///
-    /// # Notes
-    /// Assumes 20 movies per page.
-    fn create(current: u32, total: u32) -> Self {
-        let of = if total > 20 {
-            (total / 20) + (if total % 20 > 0 { 1 } else { 0 })
-        } else {
-            1
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(yts: yts_movies::Yts) -> yts_movies::Result {
+    /// let mut filter = yts_movies::Filters::default().build();
+    /// loop {
+    ///     let response = yts.search_with_filter("godfather", filter.clone()).await?;
+    ///     // ...
+    ///     match response.page.next_filter(&filter) {
+    ///         Some(next) => filter = next,
+    ///         None => break,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn next_filter(&self, filter: &Filter) -> Option<Filter> {
+        Some(Filter {
+            page: self.next?,
+            ..filter.clone()
+        })
+    }
+}
+
+impl Pagination {
+    /// Reads the page numbers of a pagination widget.
+    fn parse(widget: ElementRef, profile: &SelectorProfile) -> Self {
+        let mut pagination = Self {
+            current: widget
+                .select(&profile.compiled.pagination_current)
+                .next()
+                .and_then(|current| current.text().collect::<String>().trim().parse().ok()),
+            ..Self::default()
         };
 
-        Self { current, of, total }
+        let mut highest = pagination.current;
+        for link in widget.select(&profile.compiled.pagination_link) {
+            let Some(page) = link.attr("href").and_then(Self::page_of) else {
+                continue;
+            };
+            let text = link.text().collect::<String>().to_ascii_lowercase();
+            if text.contains("next") {
+                pagination.next = Some(page);
+            } else if text.contains("prev") {
+                pagination.previous = Some(page);
+            } else if text.contains("last") {
+                pagination.last = Some(page);
+            }
+            highest = highest.max(Some(page));
+        }
+        pagination.last = pagination.last.or(highest);
+
+        pagination
+    }
+
+    /// Returns the `page` query parameter of a link, defaulting to the first page.
+    fn page_of(href: &str) -> Option<u32> {
+        if href.starts_with("javascript:") || href == "#" {
+            return None;
+        }
+        let page = href
+            .split_once('?')
+            .and_then(|(_, query)| query.split('&').find_map(|pair| pair.strip_prefix("page=")));
+
+        match page {
+            Some(page) => page.parse().ok(),
+            None => Some(1),
+        }
     }
 }
 
@@ -50,39 +161,61 @@ pub struct Response {
 }
 
 impl Response {
+    /// Version of the serialized `Response` schema.
+    ///
+    /// Bumped whenever the serialized layout changes, so stored responses from another
+    /// release are rejected instead of misread.
+    #[cfg(feature = "serde")]
+    pub const SCHEMA_VERSION: u32 = 2;
+
     /// Parses HTML content to create a `Response` with movies and pagination info.
     ///
     /// # Parameters
     /// - `html`: Raw HTML content of the page.
     /// - `page`: Current page number.
+    /// - `profile`: Selectors locating the data in the page.
     ///
     /// # Returns
     /// A `Result` containing the parsed `Response` or an error.
     ///
     /// # Errors
-    /// Returns errors if parsing fails or required movie data is missing.
-    pub(crate) fn create(html: &str, page: u32) -> crate::Result<Self> {
+    /// Returns errors if parsing fails or required movie data is missing,
+    /// [`Error::PageOutOfRange`](crate::Error::PageOutOfRange) if `page` is past the last
+    /// page, and [`Error::LayoutChanged`](crate::Error::LayoutChanged) if the page no
+    /// longer matches the profile: no movie count, or no movie card while movies are
+    /// expected.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Response::create",
+            level = "debug",
+            skip(html, profile),
+            fields(bytes = html.len(), profile = profile.name(), total, movies)
+        )
+    )]
+    pub(crate) fn create(html: &str, page: u32, profile: &SelectorProfile) -> crate::Result<Self> {
+        let selectors = &profile.compiled;
         let document = Html::parse_document(html);
 
-        let total: u32 = document
-            .select(&Selector::parse("div.container h2 b")?)
+        let total = document
+            .select(&selectors.movie_count)
             .next()
             .and_then(|value| value.text().next())
-            .and_then(|value| value.parse().ok())
-            .unwrap_or_default();
+            .and_then(|value| value.replace(',', "").parse::<u32>().ok());
+        let list = document.select(&selectors.movie_list).next();
 
         let mut movies = Vec::new();
-        if let Some(div) = document.select(&Selector::parse("section div.row")?).next() {
-            for line in div.select(&Selector::parse("div.browse-movie-wrap")?) {
+        if let Some(div) = list {
+            for line in div.select(&selectors.movie_card) {
                 let link = line
-                    .select(&Selector::parse("a.browse-movie-link")?)
+                    .select(&selectors.movie_link)
                     .next()
                     .and_then(|e| e.attr("href"))
                     .unwrap_or_default()
                     .to_string();
 
                 let image = line
-                    .select(&Selector::parse("img")?)
+                    .select(&selectors.movie_image)
                     .next()
                     .and_then(|e| e.attr("src"))
                     .unwrap_or_default()
@@ -115,17 +248,62 @@ impl Response {
             }
         }
 
+        let pagination = document
+            .select(&selectors.pagination)
+            .next()
+            .map(|widget| Pagination::parse(widget, profile));
+        if let Some(total) = total {
+            let last = pagination
+                .as_ref()
+                .and_then(|pagination| pagination.last)
+                .unwrap_or_else(|| total.div_ceil(MOVIES_PER_PAGE))
+                .max(1);
+            if page > last {
+                return Err(crate::Error::PageOutOfRange(page, last));
+            }
+            if pagination.is_none() && total > MOVIES_PER_PAGE {
+                trace::warning!(total, "pagination not found");
+            }
+        }
+
+        let mut missing = Vec::new();
+        if total.is_none() {
+            missing.push("movie_count");
+        }
+        match list {
+            None if total != Some(0) => missing.push("movie_list"),
+            // A list with content but no card, or no card while the count promises movies.
+            Some(div)
+                if movies.is_empty()
+                    && (total.unwrap_or_default() > 0 || div.child_elements().next().is_some()) =>
+            {
+                missing.push("movie_card")
+            }
+            _ if !movies.is_empty() && movies.iter().all(|movie| movie.link.is_empty()) => {
+                missing.push("movie_link")
+            }
+            _ => {}
+        }
+        if !missing.is_empty() {
+            trace::warning!(?missing, "browse page no longer matches the selectors");
+            return Err(profile.layout_changed(&missing));
+        }
+
+        let total = total.unwrap_or_default();
+        trace::record!(total = total, movies = movies.len());
+
         Ok(Self {
-            page: Page::create(page, total),
+            page: Page::create(page, total, pagination),
             movies,
         })
     }
 }
 
 /// Represents a torrent download option for a movie.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Torrent {
-    /// The quality of the torrent (e.g., 720p, 1080p).
+    /// The quality of the torrent (e.g., 720p, 1080p.WEB, 1080p.x265).
     pub quality: Quality,
     /// The size of the torrent file.
     pub size: String,
@@ -143,7 +321,7 @@ impl Torrent {
     /// Creates a new `Torrent` instance from raw string data.
     ///
     /// # Parameters
-    /// - `quality`: Quality string (converted to `Quality` enum).
+    /// - `quality`: Parsed quality of the torrent.
     /// - `size`: Size of the torrent.
     /// - `language`: Language of the torrent.
     /// - `runtime`: Runtime of the movie.
@@ -153,7 +331,7 @@ impl Torrent {
     /// # Returns
     /// A new `Torrent` struct.
     pub(crate) fn new(
-        quality: &str,
+        quality: Quality,
         size: &str,
         language: &str,
         runtime: &str,
@@ -161,7 +339,7 @@ impl Torrent {
         link: String,
     ) -> Self {
         Self {
-            quality: quality.into(),
+            quality,
             size: size.to_string(),
             language: language.to_string(),
             runtime: runtime.to_string(),
@@ -170,28 +348,95 @@ impl Torrent {
         }
     }
 
+    /// Returns the size of the torrent in bytes, parsed from values like `1.54 GB`.
+    ///
+    /// Units are binary, so `1 GB` is 1024³ bytes. Returns `None` if the size cannot be read.
+    pub fn size_bytes(&self) -> Option<u64> {
+        let (value, unit) = self.size.trim().split_once(' ')?;
+        let value: f64 = value.parse().ok()?;
+        let multiplier = match unit.trim().to_ascii_uppercase().as_str() {
+            "B" => 1u64,
+            "KB" => 1 << 10,
+            "MB" => 1 << 20,
+            "GB" => 1 << 30,
+            "TB" => 1 << 40,
+            _ => return None,
+        };
+
+        Some((value * multiplier as f64).round() as u64)
+    }
+
+    /// Returns the runtime in minutes, parsed from values like `2 hr 55 min`.
+    ///
+    /// Returns `None` if the runtime cannot be read.
+    pub fn runtime_minutes(&self) -> Option<u32> {
+        let mut minutes = None;
+        let mut parts = self.runtime.split_whitespace();
+        while let Some(value) = parts.next() {
+            let value: u32 = value.parse().ok()?;
+            let value = match parts.next()? {
+                "hr" | "hrs" | "h" => value * 60,
+                "min" | "mins" | "m" => value,
+                _ => return None,
+            };
+            minutes = Some(minutes.unwrap_or(0) + value);
+        }
+
+        minutes
+    }
+
+    /// Returns the number of peers, parsed from the `peers / seeds` value.
+    pub fn peers(&self) -> Option<u32> {
+        self.peers_seeds_pair().map(|(peers, _)| peers)
+    }
+
+    /// Returns the number of seeds, parsed from the `peers / seeds` value.
+    pub fn seeds(&self) -> Option<u32> {
+        self.peers_seeds_pair().map(|(_, seeds)| seeds)
+    }
+
+    /// Splits `peers_seeds` into its peers and seeds counts.
+    fn peers_seeds_pair(&self) -> Option<(u32, u32)> {
+        let (peers, seeds) = self.peers_seeds.split_once('/')?;
+        Some((peers.trim().parse().ok()?, seeds.trim().parse().ok()?))
+    }
+
     /// Parses HTML content to extract a list of torrents.
     ///
     /// # Parameters
     /// - `html`: Raw HTML content containing torrent info.
+    /// - `profile`: Selectors locating the data in the page.
     ///
     /// # Returns
-    /// A `Result` containing a vector of `Torrent` structs or an error.
-    pub(crate) fn create(html: &str) -> crate::Result<Vec<Self>> {
+    /// A `Result` containing a vector of `Torrent` structs or an error. Torrents with an
+    /// unknown quality or incomplete details are skipped.
+    ///
+    /// # Errors
+    /// Returns [`Error::LayoutChanged`](crate::Error::LayoutChanged) if no torrent is found
+    /// because some selectors of the profile matched nothing or too little.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Torrent::create",
+            level = "debug",
+            skip(html, profile),
+            fields(bytes = html.len(), profile = profile.name(), torrents)
+        )
+    )]
+    pub(crate) fn create(html: &str, profile: &SelectorProfile) -> crate::Result<Vec<Self>> {
+        let selectors = &profile.compiled;
         let document = Html::parse_document(html);
 
+        let mut missing = Vec::new();
         let mut torrents = Vec::new();
-        if let Some(movie_tech_specs) = document
-            .select(&Selector::parse("div#movie-tech-specs")?)
-            .next()
-        {
+        if let Some(movie_tech_specs) = document.select(&selectors.tech_specs).next() {
             let qualities = movie_tech_specs
-                .select(&Selector::parse("span.tech-quality")?)
+                .select(&selectors.torrent_quality)
                 .map(|line| line.text().collect::<Vec<_>>())
                 .collect::<Vec<_>>();
 
             let data = movie_tech_specs
-                .select(&Selector::parse("div.tech-spec-info")?)
+                .select(&selectors.torrent_info)
                 .map(|line| {
                     line.text()
                         .map(|t| t.trim())
@@ -199,34 +444,94 @@ impl Torrent {
                         .collect::<Vec<_>>()
                 })
                 .collect::<Vec<_>>();
+            if qualities.is_empty() {
+                missing.push("torrent_quality");
+            }
+            if data.is_empty() {
+                missing.push("torrent_info");
+            }
 
-            if let Some(movie_info) = document
-                .select(&Selector::parse("div#movie-info p")?)
-                .next()
-            {
+            if let Some(movie_info) = document.select(&selectors.torrent_links).next() {
                 let data_len = data.len();
-                for (i, line) in movie_info.select(&Selector::parse("a")?).enumerate() {
+                let mut links = 0;
+                let (mut no_quality, mut short_info) = (false, false);
+                for (i, line) in movie_info.select(&selectors.torrent_link).enumerate() {
+                    links += 1;
                     let link = line.attr("href").unwrap_or_default().to_string();
 
-                    if i < data_len {
-                        let data = &data[i];
-                        let qualities = &qualities[i];
-
-                        let index = if !data[3].contains("R") { 3 } else { 4 };
-
-                        torrents.push(Torrent::new(
-                            qualities[0],
-                            data[0],
-                            data[2],
-                            data[index],
-                            data[index + 1],
-                            link,
-                        ));
-                    }
+                    let Some(data) = data.get(i) else {
+                        continue;
+                    };
+                    let Some(&label) = qualities.get(i).and_then(|quality| quality.first()) else {
+                        no_quality = true;
+                        continue;
+                    };
+
+                    let index = if data.get(3).is_some_and(|t| !t.contains("R")) {
+                        3
+                    } else {
+                        4
+                    };
+                    let (Some(size), Some(language), Some(runtime), Some(peers_seeds)) = (
+                        data.first(),
+                        data.get(2),
+                        data.get(index),
+                        data.get(index + 1),
+                    ) else {
+                        short_info = true;
+                        continue;
+                    };
+
+                    let Ok(quality) = Quality::try_from(label) else {
+                        trace::warning!(
+                            quality = label,
+                            "skipping torrent with an unknown quality"
+                        );
+                        continue;
+                    };
+
+                    torrents.push(Torrent::new(
+                        quality,
+                        size,
+                        language,
+                        runtime,
+                        peers_seeds,
+                        link,
+                    ));
+                }
+
+                if no_quality && !missing.contains(&"torrent_quality") {
+                    missing.push("torrent_quality");
+                }
+                if short_info {
+                    missing.push("torrent_info");
+                }
+                if links == 0 {
+                    missing.push("torrent_link");
+                }
+                if links != data_len {
+                    trace::warning!(
+                        details = data_len,
+                        links,
+                        "torrent links and details differ in count"
+                    );
                 }
+            } else {
+                missing.push("torrent_links");
             }
+        } else {
+            missing.push("tech_specs");
+            if document.select(&selectors.torrent_links).next().is_none() {
+                missing.push("torrent_links");
+            }
+        }
+
+        if torrents.is_empty() && !missing.is_empty() {
+            trace::warning!(?missing, "movie page no longer matches the selectors");
+            return Err(profile.layout_changed(&missing));
         }
 
+        trace::record!(torrents = torrents.len());
         Ok(torrents)
     }
 
@@ -241,3 +546,261 @@ impl Torrent {
             && !value.contains("fps")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{Filters, Genre, Quality, Resolution, Source};
+
+    use super::{Response, SelectorProfile, Torrent};
+
+    fn torrent(size: &str, runtime: &str, peers_seeds: &str) -> Torrent {
+        Torrent::new(
+            Quality::new(Resolution::P1080),
+            size,
+            "English",
+            runtime,
+            peers_seeds,
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_torrent_size_bytes() {
+        assert_eq!(torrent("1.5 GB", "", "").size_bytes(), Some(1_610_612_736));
+        assert_eq!(torrent("850 MB", "", "").size_bytes(), Some(891_289_600));
+        assert_eq!(torrent("big", "", "").size_bytes(), None);
+        assert_eq!(torrent("1.5 XB", "", "").size_bytes(), None);
+    }
+
+    #[test]
+    fn test_torrent_runtime_minutes() {
+        assert_eq!(torrent("", "2 hr 55 min", "").runtime_minutes(), Some(175));
+        assert_eq!(torrent("", "95 min", "").runtime_minutes(), Some(95));
+        assert_eq!(torrent("", "1 hr", "").runtime_minutes(), Some(60));
+        assert_eq!(torrent("", "", "").runtime_minutes(), None);
+        assert_eq!(torrent("", "2 hours", "").runtime_minutes(), None);
+    }
+
+    #[test]
+    fn test_torrent_peers_seeds() {
+        assert_eq!(torrent("", "", "12 / 340").peers(), Some(12));
+        assert_eq!(torrent("", "", "12 / 340").seeds(), Some(340));
+        assert_eq!(torrent("", "", "n/a").seeds(), None);
+    }
+
+    #[test]
+    fn test_response_create_pages() {
+        let response = Response::create(
+            include_str!("../../tests/fixtures/pages/browse-empty.html"),
+            1,
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert!(response.movies.is_empty());
+        assert_eq!((response.page.total, response.page.of), (0, 1));
+
+        let response = Response::create(
+            include_str!("../../tests/fixtures/pages/browse-thousands.html"),
+            2,
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert_eq!(response.page.current, 2);
+        assert_eq!((response.page.total, response.page.of), (1234, 62));
+        assert_eq!(
+            (response.page.previous, response.page.next),
+            (Some(1), Some(3))
+        );
+        assert_eq!(response.movies[0].name, "The Godfather");
+        assert_eq!(response.movies[0].year, 1972);
+        assert_eq!(response.movies[0].genres, [Genre::Crime, Genre::Drama]);
+
+        let response = Response::create(
+            include_str!("../../tests/fixtures/pages/browse-paginated.html"),
+            2,
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert_eq!((response.page.current, response.page.of), (2, 62));
+        assert_eq!(
+            (response.page.previous, response.page.next),
+            (Some(1), Some(3))
+        );
+        assert!(response.page.has_next());
+        assert_eq!(
+            response
+                .page
+                .next_filter(&Filters::default().genre(Genre::Crime).build()),
+            Some(Filters::default().genre(Genre::Crime).page(3).build())
+        );
+
+        let last = Response::create(
+            &include_str!("../../tests/fixtures/pages/browse-paginated.html")
+                .replace(r#"class="current">2"#, r#"class="current">62"#)
+                .replace("Next &raquo;", "")
+                .replace("Last &raquo;", ""),
+            62,
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert_eq!((last.page.current, last.page.of), (62, 62));
+        assert!(!last.page.has_next());
+        assert_eq!(last.page.next_filter(&Filters::default().build()), None);
+
+        assert!(matches!(
+            Response::create(
+                include_str!("../../tests/fixtures/pages/browse-paginated.html"),
+                63,
+                &SelectorProfile::default()
+            ),
+            Err(crate::Error::PageOutOfRange(63, 62))
+        ));
+        assert!(matches!(
+            Response::create(
+                include_str!("../../tests/fixtures/pages/browse-empty.html"),
+                2,
+                &SelectorProfile::default()
+            ),
+            Err(crate::Error::PageOutOfRange(2, 1))
+        ));
+
+        assert!(
+            Response::create(
+                include_str!("../../tests/fixtures/pages/browse-missing-year.html"),
+                1,
+                &SelectorProfile::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_torrent_create_pages() {
+        let torrents = Torrent::create(
+            include_str!("../../tests/fixtures/pages/movie.html"),
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert_eq!(torrents.len(), 3);
+        assert_eq!(
+            torrents[0].quality,
+            Quality::new(Resolution::P720).source(Source::BluRay)
+        );
+        assert_eq!(torrents[0].size, "1.15 GB");
+        assert_eq!(torrents[0].language, "English 2.0");
+        assert_eq!(torrents[0].runtime_minutes(), Some(175));
+        assert_eq!(torrents[2].seeds(), Some(97));
+
+        let torrents = Torrent::create(
+            include_str!("../../tests/fixtures/pages/movie-unrated.html"),
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert_eq!(torrents[0].runtime, "1 hr 34 min");
+        assert_eq!(torrents[0].peers_seeds, "2 / 41");
+
+        let torrents = Torrent::create(
+            include_str!("../../tests/fixtures/pages/movie-unknown-quality.html"),
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert!(torrents.is_empty());
+
+        let torrents = Torrent::create(
+            &include_str!("../../tests/fixtures/pages/movie.html")
+                .replace(">1080p.BluRay<", ">1440p.BluRay<"),
+            &SelectorProfile::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            torrents
+                .iter()
+                .map(|t| t.quality.to_string())
+                .collect::<Vec<_>>(),
+            ["720p.BluRay", "2160p.BluRay.x265"]
+        );
+    }
+
+    #[test]
+    fn test_torrent_create_layout_changed() {
+        let profile = SelectorProfile::default();
+        let missing = |html: &str| match Torrent::create(html, &profile) {
+            Err(crate::Error::LayoutChanged(_, missing)) => Some(missing),
+            _ => None,
+        };
+
+        let renamed = include_str!("../../tests/fixtures/pages/movie.html")
+            .replace(r#"class="tech-quality""#, r#"class="tech-label""#);
+        assert_eq!(missing(&renamed).as_deref(), Some("torrent_quality"));
+
+        let truncated = r#"<div id="movie-info"><p><a href="https://yts.lt/torrent/download/A1">720p.BluRay</a></p></div>
+            <div id="movie-tech-specs">
+              <span class="tech-quality">720p.BluRay</span>
+              <div class="tech-spec-info"><div>1.15 GB</div><div>1280*720</div><div>English 2.0</div></div>
+            </div>"#;
+        assert_eq!(missing(truncated).as_deref(), Some("torrent_info"));
+    }
+
+    #[test]
+    fn test_layout_changed() {
+        fn layout_changed<T>(result: crate::Result<T>) -> Option<(String, String)> {
+            match result {
+                Err(crate::Error::LayoutChanged(profile, missing)) => Some((profile, missing)),
+                _ => None,
+            }
+        }
+        let browse = include_str!("../../tests/fixtures/pages/browse-thousands.html");
+        let profile = SelectorProfile::default();
+
+        assert_eq!(
+            layout_changed(Response::create(
+                &browse.replace("browse-movie-wrap", "movie-card"),
+                1,
+                &profile
+            )),
+            Some(("yts".to_string(), "movie_card".to_string()))
+        );
+        assert_eq!(
+            layout_changed(Response::create(
+                &browse
+                    .replace("<b>1,234</b>", "<strong>1,234</strong>")
+                    .replace("<section>", "<main>"),
+                1,
+                &profile
+            ))
+            .unwrap()
+            .1,
+            "movie_count, movie_list"
+        );
+        assert_eq!(
+            layout_changed(Response::create(
+                &browse.replace("browse-movie-link", "movie-link"),
+                1,
+                &profile
+            ))
+            .unwrap()
+            .1,
+            "movie_link"
+        );
+
+        assert_eq!(
+            layout_changed(Torrent::create(
+                include_str!("../../tests/fixtures/pages/movie-no-specs.html"),
+                &profile
+            ))
+            .unwrap()
+            .1,
+            "tech_specs"
+        );
+        assert_eq!(
+            layout_changed(Torrent::create(
+                &include_str!("../../tests/fixtures/pages/movie.html")
+                    .replace("tech-spec-info", "spec-info"),
+                &profile
+            ))
+            .unwrap()
+            .1,
+            "torrent_info"
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use std::sync::{Arc, LazyLock};
+
+use scraper::Selector;
+
+/// Standard YTS layout, compiled on first use.
+static YTS: LazyLock<SelectorProfile> = LazyLock::new(|| {
+    SelectorProfile::new("yts", Selectors::default()).expect("built-in selectors should compile")
+});
+
+/// CSS selectors locating each piece of data in the site's pages.
+///
+/// Fields left out when deserializing keep the standard YTS value, so a file only needs to
+/// list the selectors that changed.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::{SelectorProfile, Selectors};
+///
+/// let profile = SelectorProfile::new(
+///     "redesign",
+///     Selectors {
+///         movie_card: "div.movie-card".to_string(),
+///         ..Selectors::default()
+///     },
+/// )?;
+/// # Ok::<(), yts_movies::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Selectors {
+    /// Element holding the number of movies found on a browse page.
+    pub movie_count: String,
+    /// Container of the movie cards on a browse page.
+    pub movie_list: String,
+    /// A movie card, inside `movie_list`.
+    pub movie_card: String,
+    /// Link to the movie page, inside `movie_card`.
+    pub movie_link: String,
+    /// Poster image, inside `movie_card`.
+    pub movie_image: String,
+    /// Container of the torrent details on a movie page.
+    pub tech_specs: String,
+    /// Quality label of a torrent, inside `tech_specs`.
+    pub torrent_quality: String,
+    /// Details of a torrent, inside `tech_specs`.
+    pub torrent_info: String,
+    /// Container of the torrent download links on a movie page.
+    pub torrent_links: String,
+    /// A torrent download link, inside `torrent_links`.
+    pub torrent_link: String,
+    /// Link to the IMDb page of the movie, on a movie page.
+    pub imdb_link: String,
+    /// Pagination widget of a browse page, whose links carry a `page` query parameter.
+    pub pagination: String,
+    /// Current page, inside `pagination`.
+    pub pagination_current: String,
+    /// A link to another page, inside `pagination`.
+    pub pagination_link: String,
+}
+
+/// A set of [`Selectors`], compiled once and cheap to clone.
+///
+/// Clients use the standard YTS profile, the only built-in one, whatever their mirror, and
+/// can be given another one with `with_selectors`, e.g. loaded at runtime with
+/// `SelectorProfile::from_json`, or from any other format deserializing [`Selectors`],
+/// through the `serde` feature.
+#[derive(Debug, Clone)]
+pub struct SelectorProfile {
+    /// Name of the profile, for diagnostics.
+    name: Arc<str>,
+    /// Source of the selectors.
+    selectors: Arc<Selectors>,
+    /// Compiled selectors.
+    pub(crate) compiled: Arc<Compiled>,
+}
+
+/// Compiled counterpart of [`Selectors`].
+#[derive(Debug)]
+pub(crate) struct Compiled {
+    pub(crate) movie_count: Selector,
+    pub(crate) movie_list: Selector,
+    pub(crate) movie_card: Selector,
+    pub(crate) movie_link: Selector,
+    pub(crate) movie_image: Selector,
+    pub(crate) tech_specs: Selector,
+    pub(crate) torrent_quality: Selector,
+    pub(crate) torrent_info: Selector,
+    pub(crate) torrent_links: Selector,
+    pub(crate) torrent_link: Selector,
+    pub(crate) imdb_link: Selector,
+    pub(crate) pagination: Selector,
+    pub(crate) pagination_current: Selector,
+    pub(crate) pagination_link: Selector,
+}
+
+impl Default for Selectors {
+    /// Returns the selectors of the standard YTS layout.
+    fn default() -> Self {
+        Self {
+            movie_count: "div.container h2 b".to_string(),
+            movie_list: "section div.row".to_string(),
+            movie_card: "div.browse-movie-wrap".to_string(),
+            movie_link: "a.browse-movie-link".to_string(),
+            movie_image: "img".to_string(),
+            tech_specs: "div#movie-tech-specs".to_string(),
+            torrent_quality: "span.tech-quality".to_string(),
+            torrent_info: "div.tech-spec-info".to_string(),
+            torrent_links: "div#movie-info p".to_string(),
+            torrent_link: "a".to_string(),
+            imdb_link: r#"a[href*="imdb.com/title/"]"#.to_string(),
+            pagination: "ul.tsc_pagination".to_string(),
+            pagination_current: "a.current".to_string(),
+            pagination_link: "a".to_string(),
+        }
+    }
+}
+
+impl Default for SelectorProfile {
+    /// Returns the built-in profile of the standard YTS layout.
+    fn default() -> Self {
+        YTS.clone()
+    }
+}
+
+impl SelectorProfile {
+    /// Compiles `selectors` into a profile called `name`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidSelector`](crate::Error::InvalidSelector) naming the first
+    /// selector that is not valid CSS.
+    pub fn new(name: &str, selectors: Selectors) -> crate::Result<Self> {
+        let compile = |field: &str, css: &str| {
+            Selector::parse(css)
+                .map_err(|e| crate::Error::InvalidSelector(format!("{field} `{css}`: {e}")))
+        };
+
+        let compiled = Compiled {
+            movie_count: compile("movie_count", &selectors.movie_count)?,
+            movie_list: compile("movie_list", &selectors.movie_list)?,
+            movie_card: compile("movie_card", &selectors.movie_card)?,
+            movie_link: compile("movie_link", &selectors.movie_link)?,
+            movie_image: compile("movie_image", &selectors.movie_image)?,
+            tech_specs: compile("tech_specs", &selectors.tech_specs)?,
+            torrent_quality: compile("torrent_quality", &selectors.torrent_quality)?,
+            torrent_info: compile("torrent_info", &selectors.torrent_info)?,
+            torrent_links: compile("torrent_links", &selectors.torrent_links)?,
+            torrent_link: compile("torrent_link", &selectors.torrent_link)?,
+            imdb_link: compile("imdb_link", &selectors.imdb_link)?,
+            pagination: compile("pagination", &selectors.pagination)?,
+            pagination_current: compile("pagination_current", &selectors.pagination_current)?,
+            pagination_link: compile("pagination_link", &selectors.pagination_link)?,
+        };
+
+        Ok(Self {
+            name: name.into(),
+            selectors: Arc::new(selectors),
+            compiled: Arc::new(compiled),
+        })
+    }
+
+    /// Compiles the selectors of the JSON object `json` into a profile called `name`.
+    ///
+    /// Fields left out keep the standard YTS value, so the object only needs to list the
+    /// selectors that changed, e.g. `{ "movie_card": "article.movie" }`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidSelector`](crate::Error::InvalidSelector) if `json` is not
+    /// an object of selectors or a selector is not valid CSS.
+    #[cfg(feature = "serde")]
+    pub fn from_json(name: &str, json: &str) -> crate::Result<Self> {
+        let invalid = |e: serde_json::Error| crate::Error::InvalidSelector(format!("{name}: {e}"));
+
+        let object = serde_json::from_str::<serde_json::Map<_, _>>(json).map_err(invalid)?;
+        let selectors =
+            serde::Deserialize::deserialize(serde_json::Value::Object(object)).map_err(invalid)?;
+        Self::new(name, selectors)
+    }
+
+    /// Returns the name of the profile.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the selectors of the profile.
+    pub fn selectors(&self) -> &Selectors {
+        &self.selectors
+    }
+
+    /// Returns the error reporting that the `missing` selectors matched nothing in a page.
+    pub(crate) fn layout_changed(&self, missing: &[&str]) -> crate::Error {
+        crate::Error::LayoutChanged(self.name.to_string(), missing.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Response;
+
+    use super::{SelectorProfile, Selectors};
+
+    #[test]
+    fn test_selector_profile_new() {
+        let profile = SelectorProfile::new(
+            "custom",
+            Selectors {
+                movie_card: "div.movie-card".to_string(),
+                ..Selectors::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(profile.name(), "custom");
+        assert_eq!(profile.selectors().movie_card, "div.movie-card");
+        assert_eq!(profile.selectors().movie_link, "a.browse-movie-link");
+
+        let error = SelectorProfile::new(
+            "broken",
+            Selectors {
+                torrent_info: "div..info".to_string(),
+                ..Selectors::default()
+            },
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&error, crate::Error::InvalidSelector(message) if message.starts_with("torrent_info"))
+        );
+    }
+
+    #[test]
+    fn test_selector_profile_parses_changed_layout() {
+        let html = include_str!("../../tests/fixtures/pages/browse-thousands.html")
+            .replace("browse-movie-wrap", "movie-card");
+        let profile = SelectorProfile::new(
+            "redesign",
+            Selectors {
+                movie_card: "div.movie-card".to_string(),
+                ..Selectors::default()
+            },
+        )
+        .unwrap();
+
+        let standard = Response::create(&html, 1, &SelectorProfile::default());
+        let redesign = Response::create(&html, 1, &profile).unwrap();
+
+        assert!(matches!(standard, Err(crate::Error::LayoutChanged(..))));
+        assert_eq!(redesign.movies[0].name, "The Godfather");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_selector_profile_from_json() {
+        let profile =
+            SelectorProfile::from_json("redesign", r#"{ "movie_card": "article.movie" }"#).unwrap();
+        assert_eq!(profile.name(), "redesign");
+        assert_eq!(profile.selectors().movie_card, "article.movie");
+        assert_eq!(profile.selectors().movie_link, "a.browse-movie-link");
+
+        for json in [
+            r#"{ "movie_card": 3 }"#,
+            r#"["div"]"#,
+            "not json",
+            r#"{ "tech_specs": "div##specs" }"#,
+        ] {
+            assert!(
+                matches!(
+                    SelectorProfile::from_json("broken", json),
+                    Err(crate::Error::InvalidSelector(_))
+                ),
+                "{json}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_selectors_partial_override() {
+        let selectors: Selectors =
+            serde_json::from_str(r#"{ "movie_card": "article.movie" }"#).unwrap();
+
+        assert_eq!(selectors.movie_card, "article.movie");
+        assert_eq!(selectors.tech_specs, Selectors::default().tech_specs);
+    }
+}
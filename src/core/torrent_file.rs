@@ -0,0 +1,569 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{
+    InfoHash, InfoHashV2,
+    bencode::{self, Value},
+};
+use crate::{Codec, Resolution, Source, Torrent};
+
+/// Largest relative difference accepted between the size of the files and the size shown
+/// on the movie page, which is rounded to two decimals.
+const SIZE_TOLERANCE: f64 = 0.02;
+
+/// Contents of a `.torrent` file, as described by BEP 3 and, for v2 and hybrid torrents,
+/// BEP 52.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::TorrentFile;
+///
+/// let bytes = b"d8:announce33:udp://tracker.local:1337/announce4:infod6:lengthi1234e\
+/// 4:name27:The Godfather (1972) [720p]12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaaee";
+///
+/// let torrent_file = TorrentFile::parse(bytes)?;
+/// assert_eq!(torrent_file.name, "The Godfather (1972) [720p]");
+/// assert_eq!(torrent_file.total_size(), 1234);
+/// assert_eq!(torrent_file.trackers(), ["udp://tracker.local:1337/announce"]);
+/// # Ok::<(), yts_movies::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorrentFile {
+    /// v1 info hash, for v1 and hybrid torrents.
+    pub info_hash: Option<InfoHash>,
+    /// v2 info hash, for v2 and hybrid torrents.
+    pub info_hash_v2: Option<InfoHashV2>,
+    /// Name of the file, or of the directory holding the files.
+    pub name: String,
+    /// Files of the torrent, without padding files.
+    pub files: Vec<FileEntry>,
+    /// Number of bytes in each piece.
+    pub piece_length: u64,
+    /// Tracker URLs in tiers, from `announce-list`, else the single `announce` URL.
+    pub announce: Vec<Vec<String>>,
+    /// When the torrent was created, if recorded.
+    pub creation_date: Option<SystemTime>,
+}
+
+/// A file of a torrent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    /// Path below the torrent name, one component per item. A single-file torrent lists
+    /// its name.
+    pub path: Vec<String>,
+    /// Size in bytes.
+    pub length: u64,
+}
+
+/// Difference between a torrent file and the [`Torrent`] scraped from the movie page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The files add up to another size than shown on the page, or that size is unreadable.
+    Size {
+        /// Size shown on the page, in bytes.
+        expected: Option<u64>,
+        /// Total size of the files, in bytes.
+        actual: u64,
+    },
+    /// The torrent name gives another resolution, or none.
+    Resolution {
+        /// Resolution shown on the page.
+        expected: Resolution,
+        /// Resolution in the torrent name.
+        actual: Option<Resolution>,
+    },
+    /// The torrent name gives another codec. An unlabelled codec is x264.
+    Codec {
+        /// Codec shown on the page.
+        expected: Option<Codec>,
+        /// Codec in the torrent name.
+        actual: Option<Codec>,
+    },
+    /// The torrent name gives another source.
+    Source {
+        /// Source shown on the page.
+        expected: Source,
+        /// Source in the torrent name.
+        actual: Source,
+    },
+}
+
+impl TorrentFile {
+    /// Parses the bytes of a `.torrent` file.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidTorrent`](crate::Error::InvalidTorrent) if `bytes` is not
+    /// bencoded, or lacks the name, piece length, pieces or files of a v1 or v2 torrent.
+    pub fn parse(bytes: &[u8]) -> crate::Result<Self> {
+        let metainfo = bencode::decode(bytes)?;
+        let info = metainfo
+            .get("info")
+            .filter(|info| info.is_dict())
+            .ok_or_else(|| invalid("missing info dictionary"))?;
+
+        let name = info
+            .get("name")
+            .and_then(Value::bytes)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .filter(|name| is_safe(name))
+            .ok_or_else(|| invalid("missing or unsafe name"))?;
+        let piece_length = info
+            .get("piece length")
+            .and_then(Value::int)
+            .and_then(|length| u64::try_from(length).ok())
+            .filter(|&length| length > 0)
+            .ok_or_else(|| invalid("missing piece length"))?;
+
+        let v1 = match info.get("pieces") {
+            Some(pieces) => match pieces.bytes() {
+                Some(pieces) if pieces.len() % 20 == 0 => true,
+                _ => return Err(invalid("pieces not a list of SHA-1 hashes")),
+            },
+            None => false,
+        };
+        let v2 = info.get("meta version").and_then(Value::int) == Some(2);
+        if !v1 && !v2 {
+            return Err(invalid("missing pieces"));
+        }
+
+        let files = match (info.get("length"), info.get("files"), info.get("file tree")) {
+            (Some(length), _, _) if v1 => vec![FileEntry {
+                path: vec![name.clone()],
+                length: length_of(length)?,
+            }],
+            (None, Some(files), _) if v1 => v1_files(files)?,
+            (_, _, Some(tree)) if v2 => {
+                let mut files = Vec::new();
+                v2_files(tree, &mut Vec::new(), &mut files)?;
+                files
+            }
+            _ => return Err(invalid("missing files")),
+        };
+
+        Ok(Self {
+            info_hash: v1.then(|| InfoHash::of(info.raw)),
+            info_hash_v2: v2.then(|| InfoHashV2::of(info.raw)),
+            name,
+            files,
+            piece_length,
+            announce: announce(&metainfo),
+            creation_date: metainfo
+                .get("creation date")
+                .and_then(Value::int)
+                .and_then(|seconds| u64::try_from(seconds).ok())
+                .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds)),
+        })
+    }
+
+    /// Returns the total size of the files, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.length).sum()
+    }
+
+    /// Returns every tracker URL once, in tier order.
+    pub fn trackers(&self) -> Vec<&str> {
+        let mut trackers: Vec<&str> = Vec::new();
+        for url in self.announce.iter().flatten() {
+            if !trackers.contains(&url.as_str()) {
+                trackers.push(url);
+            }
+        }
+        trackers
+    }
+
+    /// Returns how this torrent file differs from the `torrent` scraped from the movie page.
+    ///
+    /// The total size must be within 2% of the size shown on the page. The torrent name,
+    /// e.g. `The Godfather (1972) [1080p] [BluRay] [x265]`, must give the same resolution,
+    /// and the codec and source it gives, if any, must match.
+    pub fn mismatches(&self, torrent: &Torrent) -> Vec<Mismatch> {
+        let mut mismatches = Vec::new();
+
+        let expected = torrent.size_bytes();
+        let actual = self.total_size();
+        let within = |expected: u64| {
+            (actual as f64 - expected as f64).abs() <= expected as f64 * SIZE_TOLERANCE
+        };
+        if !expected.is_some_and(within) {
+            mismatches.push(Mismatch::Size { expected, actual });
+        }
+
+        let labels = self
+            .name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|label| !label.is_empty());
+        let (mut resolution, mut codec, mut source) = (None, None, None);
+        for label in labels {
+            if let Ok(value) = Resolution::try_from(label) {
+                resolution = Some(value);
+            } else if let Ok(value) = Codec::try_from(label) {
+                codec = Some(value);
+            } else if let Ok(value) = Source::try_from(label) {
+                source = Some(value);
+            }
+        }
+
+        let quality = torrent.quality;
+        if resolution != Some(quality.resolution) {
+            mismatches.push(Mismatch::Resolution {
+                expected: quality.resolution,
+                actual: resolution,
+            });
+        }
+        let labelled = |codec: Option<Codec>| codec.filter(|&codec| codec != Codec::X264);
+        if labelled(codec) != labelled(quality.codec) {
+            mismatches.push(Mismatch::Codec {
+                expected: quality.codec,
+                actual: codec,
+            });
+        }
+        if let (Some(expected), Some(actual)) = (quality.source, source)
+            && expected != actual
+        {
+            mismatches.push(Mismatch::Source { expected, actual });
+        }
+
+        mismatches
+    }
+
+    /// Returns `true` if this torrent file matches the `torrent` scraped from the movie
+    /// page, see [`TorrentFile::mismatches`].
+    pub fn matches(&self, torrent: &Torrent) -> bool {
+        self.mismatches(torrent).is_empty()
+    }
+}
+
+impl std::fmt::Display for Mismatch {
+    /// Explains the mismatch, e.g. `resolution 1080p, expected 720p`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = |label: Option<&str>| label.unwrap_or("none").to_string();
+        match self {
+            Mismatch::Size {
+                expected: Some(expected),
+                actual,
+            } => write!(f, "size {actual} bytes, expected {expected} bytes"),
+            Mismatch::Size {
+                expected: None,
+                actual,
+            } => write!(f, "size {actual} bytes, expected size unknown"),
+            Mismatch::Resolution { expected, actual } => {
+                let expected: &str = expected.into();
+                let actual = name(actual.as_ref().map(Into::into));
+                write!(f, "resolution {actual}, expected {expected}")
+            }
+            Mismatch::Codec { expected, actual } => {
+                let expected = name(expected.as_ref().map(Into::into));
+                let actual = name(actual.as_ref().map(Into::into));
+                write!(f, "codec {actual}, expected {expected}")
+            }
+            Mismatch::Source { expected, actual } => {
+                let expected: &str = expected.into();
+                let actual: &str = actual.into();
+                write!(f, "source {actual}, expected {expected}")
+            }
+        }
+    }
+}
+
+/// Returns the error for a torrent file with a `problem`.
+fn invalid(problem: &str) -> crate::Error {
+    crate::Error::InvalidTorrent(problem.to_string())
+}
+
+/// Returns `true` if `component` is a single path component, not `.` or `..`.
+fn is_safe(component: &str) -> bool {
+    !matches!(component, "" | "." | "..") && !component.contains(['/', '\\', '\0'])
+}
+
+/// Reads a file length.
+fn length_of(value: &Value) -> crate::Result<u64> {
+    value
+        .int()
+        .and_then(|length| u64::try_from(length).ok())
+        .ok_or_else(|| invalid("invalid file length"))
+}
+
+/// Reads the `files` list of a v1 multi-file torrent, skipping BEP 47 padding files.
+fn v1_files(files: &Value) -> crate::Result<Vec<FileEntry>> {
+    let files = files.list().ok_or_else(|| invalid("files not a list"))?;
+
+    let mut entries = Vec::new();
+    for file in files {
+        let padding = file
+            .get("attr")
+            .and_then(Value::bytes)
+            .is_some_and(|attr| attr.contains(&b'p'));
+        if padding {
+            continue;
+        }
+
+        let path = file
+            .get("path")
+            .and_then(Value::list)
+            .filter(|path| !path.is_empty())
+            .ok_or_else(|| invalid("missing file path"))?
+            .iter()
+            .map(|component| {
+                component
+                    .bytes()
+                    .map(|component| String::from_utf8_lossy(component).into_owned())
+                    .filter(|component| is_safe(component))
+                    .ok_or_else(|| invalid("unsafe file path"))
+            })
+            .collect::<crate::Result<_>>()?;
+        let length = file
+            .get("length")
+            .ok_or_else(|| invalid("missing file length"))?;
+
+        entries.push(FileEntry {
+            path,
+            length: length_of(length)?,
+        });
+    }
+    Ok(entries)
+}
+
+/// Reads a v2 `file tree`, where each file is a dictionary keyed by an empty string.
+fn v2_files(tree: &Value, path: &mut Vec<String>, files: &mut Vec<FileEntry>) -> crate::Result {
+    let entries = tree
+        .dict()
+        .ok_or_else(|| invalid("file tree not a dictionary"))?;
+
+    for (key, node) in entries {
+        if key.is_empty() {
+            if path.is_empty() {
+                return Err(invalid("file without a path"));
+            }
+            let length = node
+                .get("length")
+                .ok_or_else(|| invalid("missing file length"))?;
+            files.push(FileEntry {
+                path: path.clone(),
+                length: length_of(length)?,
+            });
+            continue;
+        }
+
+        let component = String::from_utf8_lossy(key).into_owned();
+        if !is_safe(&component) {
+            return Err(invalid("unsafe file path"));
+        }
+        path.push(component);
+        v2_files(node, path, files)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+/// Reads the tracker tiers of a torrent, ignoring URLs that are not text.
+fn announce(metainfo: &Value) -> Vec<Vec<String>> {
+    let urls = |tier: &[Value]| {
+        tier.iter()
+            .filter_map(Value::str)
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    let tiers = metainfo
+        .get("announce-list")
+        .and_then(Value::list)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Value::list)
+        .map(urls)
+        .filter(|tier| !tier.is_empty())
+        .collect::<Vec<_>>();
+    if !tiers.is_empty() {
+        return tiers;
+    }
+
+    metainfo
+        .get("announce")
+        .and_then(Value::str)
+        .map(|url| vec![vec![url.to_string()]])
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{Codec, Quality, Resolution, Source, Torrent};
+
+    use super::{FileEntry, Mismatch, TorrentFile};
+
+    /// Single-file v1 torrent with tracker tiers and a creation date.
+    const SINGLE: &[u8] = b"d8:announce27:udp://a.local:1337/announce13:announce-listll27:udp://a.local:1337/announceel27:udp://b.local:6969/announce27:udp://a.local:1337/announceee13:creation datei1600000000e4:infod6:lengthi2609192960e4:name37:The Godfather (1972) [1080p] [BluRay]12:piece lengthi1048576e6:pieces20:aaaaaaaaaaaaaaaaaaaaee";
+    /// Multi-file v1 torrent with a padding file.
+    const MULTI: &[u8] = b"d8:announce23:http://a.local/announce4:infod5:filesld6:lengthi1000e4:pathl9:Movie.mp4eed4:attr1:p6:lengthi512e4:pathl4:.pad3:512eed6:lengthi24e4:pathl4:Subs11:English.srteee4:name5:Movie12:piece lengthi16384e6:pieces20:bbbbbbbbbbbbbbbbbbbbee";
+    /// Hybrid v1 and v2 torrent.
+    const HYBRID: &[u8] = b"d4:infod9:file treed9:Movie.mp4d0:d6:lengthi1000e11:pieces root32:rrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrree4:Subsd11:English.srtd0:d6:lengthi24eeeee5:filesld6:lengthi1000e4:pathl9:Movie.mp4eed6:lengthi24e4:pathl4:Subs11:English.srteee12:meta versioni2e4:name5:Movie12:piece lengthi16384e6:pieces20:ccccccccccccccccccccee";
+    /// v2-only torrent.
+    const V2: &[u8] = b"d4:infod9:file treed9:Movie.mp4d0:d6:lengthi1000e11:pieces root32:rrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrree4:Subsd11:English.srtd0:d6:lengthi24eeeee12:meta versioni2e4:name5:Movie12:piece lengthi16384eee";
+
+    fn torrent(quality: Quality, size: &str) -> Torrent {
+        Torrent::new(
+            quality,
+            size,
+            "English",
+            "2 hr 55 min",
+            "1 / 2",
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_parse_single_file() {
+        let torrent_file = TorrentFile::parse(SINGLE).unwrap();
+
+        assert_eq!(
+            torrent_file.info_hash.unwrap().to_string(),
+            "b58944f0ff67cd54076045e8a2a895ec68103fdc"
+        );
+        assert_eq!(torrent_file.info_hash_v2, None);
+        assert_eq!(torrent_file.name, "The Godfather (1972) [1080p] [BluRay]");
+        assert_eq!(
+            torrent_file.files,
+            [FileEntry {
+                path: vec!["The Godfather (1972) [1080p] [BluRay]".to_string()],
+                length: 2_609_192_960,
+            }]
+        );
+        assert_eq!(torrent_file.piece_length, 1 << 20);
+        assert_eq!(
+            torrent_file.announce,
+            [
+                vec!["udp://a.local:1337/announce".to_string()],
+                vec![
+                    "udp://b.local:6969/announce".to_string(),
+                    "udp://a.local:1337/announce".to_string()
+                ],
+            ]
+        );
+        assert_eq!(
+            torrent_file.trackers(),
+            ["udp://a.local:1337/announce", "udp://b.local:6969/announce"]
+        );
+        assert_eq!(
+            torrent_file.creation_date,
+            Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+        );
+    }
+
+    #[test]
+    fn test_parse_multi_file() {
+        let torrent_file = TorrentFile::parse(MULTI).unwrap();
+
+        assert_eq!(
+            torrent_file.files,
+            [
+                FileEntry {
+                    path: vec!["Movie.mp4".to_string()],
+                    length: 1000,
+                },
+                FileEntry {
+                    path: vec!["Subs".to_string(), "English.srt".to_string()],
+                    length: 24,
+                },
+            ]
+        );
+        assert_eq!(torrent_file.total_size(), 1024);
+        assert_eq!(
+            torrent_file.announce,
+            [vec!["http://a.local/announce".to_string()]]
+        );
+        assert_eq!(torrent_file.creation_date, None);
+    }
+
+    #[test]
+    fn test_parse_hybrid() {
+        let torrent_file = TorrentFile::parse(HYBRID).unwrap();
+
+        assert_eq!(
+            torrent_file.info_hash.unwrap().to_string(),
+            "8cc2d2d8f51d64a254326e721f8db839412de051"
+        );
+        assert_eq!(
+            torrent_file.info_hash_v2.unwrap().to_string(),
+            "3827ce3a1519246e63c5c292ec3b3de59689ed12d69b5b02932c0ea07a9cdf39"
+        );
+        assert_eq!(torrent_file.total_size(), 1024);
+
+        let torrent_file = TorrentFile::parse(V2).unwrap();
+        assert_eq!(torrent_file.info_hash, None);
+        assert!(torrent_file.info_hash_v2.is_some());
+        assert_eq!(
+            torrent_file.files,
+            [
+                FileEntry {
+                    path: vec!["Movie.mp4".to_string()],
+                    length: 1000,
+                },
+                FileEntry {
+                    path: vec!["Subs".to_string(), "English.srt".to_string()],
+                    length: 24,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for invalid in [
+            &b"<html></html>"[..],
+            b"d8:announce3:urle",
+            b"d4:infod4:name1:a12:piece lengthi16ee",
+            b"d4:infod6:lengthi1e4:name1:a12:piece lengthi16e6:pieces3:abcee",
+            b"d4:infod6:lengthi1e4:name2:..12:piece lengthi16e6:pieces0:ee",
+            b"d4:infod5:filesld6:lengthi1e4:pathl2:..eee4:name1:a12:piece lengthi16e6:pieces0:ee",
+        ] {
+            assert!(
+                matches!(
+                    TorrentFile::parse(invalid),
+                    Err(crate::Error::InvalidTorrent(_))
+                ),
+                "{}",
+                String::from_utf8_lossy(invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let torrent_file = TorrentFile::parse(SINGLE).unwrap();
+        let quality = Quality::new(Resolution::P1080).source(Source::BluRay);
+
+        assert!(torrent_file.matches(&torrent(quality, "2.43 GB")));
+        assert!(torrent_file.matches(&torrent(Quality::new(Resolution::P1080), "2.4 GB")));
+        assert_eq!(
+            torrent_file.mismatches(&torrent(
+                Quality::new(Resolution::P720)
+                    .source(Source::Web)
+                    .codec(Codec::X265),
+                "1.2 GB"
+            )),
+            [
+                Mismatch::Size {
+                    expected: Some(1_288_490_189),
+                    actual: 2_609_192_960,
+                },
+                Mismatch::Resolution {
+                    expected: Resolution::P720,
+                    actual: Some(Resolution::P1080),
+                },
+                Mismatch::Codec {
+                    expected: Some(Codec::X265),
+                    actual: None,
+                },
+                Mismatch::Source {
+                    expected: Source::Web,
+                    actual: Source::BluRay,
+                },
+            ]
+        );
+        assert_eq!(
+            torrent_file.mismatches(&torrent(quality, "unknown"))[0].to_string(),
+            "size 2609192960 bytes, expected size unknown"
+        );
+    }
+}
@@ -6,12 +6,18 @@
 //! ## Features
 //! - Async and blocking HTTP clients (enabled via feature flags `async` and `blocking`).
 //! - Rich filtering options such as quality, genre, rating, year, and sorting order.
+//! - Client-side post-filters on genres, seeds, size, runtime, year and title.
+//! - Opt-in in-memory cache with TTL, LRU eviction and hit/miss statistics.
+//! - Opt-in disk cache revalidated with conditional requests, usable offline.
+//! - Opt-in rate limiting with bursts, a concurrency cap and jittered delays.
+//! - HTTP(S) and SOCKS5 proxies, from the environment or per mirror host.
+//! - Pluggable [`Observer`] of requests, cache hits and parse failures, e.g. for metrics.
 //! - Parsing of HTML responses to extract movie and torrent metadata.
 //!
 //!
 //! ### Async Example (default)
 //!
-//! ```
+//! ```no_run
 //! # #[cfg(feature = "async")]
 //! use yts_movies::{Filters, OrderBy, Year, Yts};
 //!
@@ -44,9 +50,26 @@
 //! ## Modules & Re-exports
 //!
 //! The crate re-exports key types for convenience:
-//! - Filtering options: [`Filters`], [`OrderBy`], [`Quality`], [`Rating`], [`Year`]
-//! - Core types: [`Page`], [`Response`], [`Torrent`], [`Genre`], [`Movie`]
+//! - Filtering options: [`Filters`], [`Filter`], [`PostFilter`], [`OrderBy`], [`Direction`], [`Quality`], [`Resolution`], [`Codec`], [`Source`], [`Rating`], [`Year`]
+//! - Core types: [`Page`], [`Response`], [`Torrent`], [`Genre`], [`Movie`], and the [`Match`]
+//!   of a fuzzy lookup
+//! - Torrent selection: [`TorrentPolicy`] picking a [`Selection`], with the [`Rejection`] of
+//!   each [`Rejected`] torrent
+//! - Parsing: [`SelectorProfile`] compiled from [`Selectors`], overridable at runtime, and
+//!   the [`SelfTest`] report detecting layout changes
 //! - Client structs: [`Yts`] (async) and blocking client (behind feature flags)
+//! - Caching: [`Cache`] and its [`CacheStats`], and the persistent [`DiskCache`]
+//! - Politeness: [`RateLimiter`] shared by the clients
+//! - Crawling: [`CrawlLimits`] bounding a `search_all` crawl, returning a [`Crawl`] with its
+//!   [`CrawlStats`]
+//! - Networking: [`Proxies`] routing requests through a [`Proxy`]
+//! - Downloads: [`DownloadedTorrent`] saved by `download_torrent`, with its [`InfoHash`]
+//! - Torrent files: [`TorrentFile`] parsed from `.torrent` bytes, listing each [`FileEntry`],
+//!   with its [`InfoHashV2`] and any [`Mismatch`] with the scraped [`Torrent`]
+//! - Trackers: [`ScrapeLimits`] bounding a tracker `scrape`, returning a [`Scrape`] with the
+//!   [`SwarmHealth`] reported by each tracker
+//! - Testing: [`Cassette`] to record and replay responses, and the `test_util` fake server
+//!   and tracker
 //!
 //! ## Error Handling
 //!
@@ -57,6 +80,10 @@
 //!
 //! - `async` — Enables the asynchronous API (`search`).
 //! - `blocking` — Enables the blocking (synchronous) API (`blocking::search`).
+//! - `serde` — Implements `Serialize` and `Deserialize` for the model and filter types.
+//! - `tracing` — Emits [`tracing`](https://docs.rs/tracing) spans for requests and parsing.
+//! - `test-util` — Provides `test_util::FakeYts`, a local fake YTS server, and
+//!   `test_util::FakeTracker`, a local fake tracker, for tests.
 //!
 //! ## License
 //!
@@ -69,10 +96,21 @@
 
 mod client;
 mod core;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+mod trace;
 
-pub use client::{Filters, OrderBy, Quality, Rating, Year};
+pub use client::{
+    Cache, CacheKind, CacheStats, Cassette, CassetteMode, Codec, Crawl, CrawlLimits, CrawlStats,
+    Direction, DiskCache, DownloadedTorrent, Endpoint, Filter, Filters, Observer, OrderBy,
+    PostFilter, Proxies, Proxy, Quality, RateLimiter, Rating, Resolution, Scrape, ScrapeLimits,
+    SelfTest, Source, SwarmHealth, Year,
+};
 pub use core::{
-    Page, Response, Torrent,
+    FileEntry, InfoHash, InfoHashV2, Match, Mismatch, Page, Rejected, Rejection, Response,
+    Selection, SelectorProfile, Selectors, Torrent, TorrentFile, TorrentPolicy,
     model::{Genre, Movie},
 };
 
@@ -97,8 +135,10 @@ pub enum Error {
     ToStrError(#[from] reqwest::header::ToStrError),
 
     /// Error parsing CSS selectors during HTML scraping.
-    #[error(transparent)]
-    SelectorError(#[from] scraper::error::SelectorErrorKind<'static>),
+    ///
+    /// Holds the message of the parser error, which itself cannot cross threads.
+    #[error("Error parsing CSS selector: {0}")]
+    SelectorError(String),
 
     /// Error parsing a floating point number.
     #[error(transparent)]
@@ -120,11 +160,112 @@ pub enum Error {
     #[error("Error getting movie name")]
     MovieNameError,
 
+    /// Error indicating a year filter the site cannot serve.
+    #[error("Invalid year range {0}-{1}")]
+    YearRangeError(u32, u32),
+
+    /// Error indicating a filter a browse URL cannot hold exactly, e.g. a custom year
+    /// range.
+    #[error("No browse url for {0}")]
+    NoBrowseUrl(String),
+
+    /// Error parsing a browse URL into a filter.
+    #[error("Error parsing browse url: {0}")]
+    BrowseUrlError(String),
+
+    /// Error compiling a regular expression.
+    #[error(transparent)]
+    RegexError(#[from] regex::Error),
+
+    /// Error reading or writing the disk cache.
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    /// Error indicating a page missing from the disk cache in cache-only mode.
+    #[error("Page not found in disk cache {0}")]
+    NotCachedError(String),
+
+    /// Error indicating a non-success HTTP status.
+    #[error("Unexpected HTTP status {0} for {1}")]
+    StatusError(u16, String),
+
+    /// Error configuring or connecting through a proxy.
+    #[error("Proxy error: {0}")]
+    ProxyError(String),
+
+    /// Error reading a cassette or replaying a request it does not contain.
+    #[error("Cassette error: {0}")]
+    CassetteError(String),
+
+    /// Error compiling a CSS selector of a [`SelectorProfile`].
+    #[error("Invalid CSS selector {0}")]
+    InvalidSelector(String),
+
+    /// Error indicating a page no longer matching the selectors of a [`SelectorProfile`],
+    /// likely after a redesign of the site. Holds the profile name and a fingerprint of the
+    /// selectors that matched nothing, e.g. `movie_card`.
+    #[error("Page layout changed for selector profile {0}, no match for {1}")]
+    LayoutChanged(String, String),
+
+    /// Error indicating a requested page past the last page of the results.
+    #[error("Page {0} is past the last page {1}")]
+    PageOutOfRange(u32, u32),
+
+    /// Error indicating a malformed IMDb title id, which must look like `tt0068646`.
+    #[error("Invalid IMDb id {0}")]
+    InvalidImdbId(String),
+
+    /// Error indicating no movie found for an IMDb title id.
+    #[error("No movie found for IMDb id {0}")]
+    MovieNotFound(String),
+
+    /// Error indicating a `.torrent` file that is not valid bencode or lacks its metadata.
+    #[error("Invalid torrent file: {0}")]
+    InvalidTorrent(String),
+
+    /// Error from a tracker: an error answer, a malformed or missing response, or a
+    /// timeout.
+    #[error("Tracker error: {0}")]
+    TrackerError(String),
+
     /// Error parsing an url.
     #[error("Error parsing url {0}")]
     ParseError(String),
 }
 
+impl Error {
+    /// Returns the name of the variant, a stable label for metrics, e.g. `MovieYearError`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Error::ReqwestError(_) => "ReqwestError",
+            Error::ToStrError(_) => "ToStrError",
+            Error::SelectorError(_) => "SelectorError",
+            Error::ParseFloatError(_) => "ParseFloatError",
+            Error::ParseIntError(_) => "ParseIntError",
+            Error::MovieRatingError => "MovieRatingError",
+            Error::MovieYearError => "MovieYearError",
+            Error::MovieNameError => "MovieNameError",
+            Error::YearRangeError(..) => "YearRangeError",
+            Error::BrowseUrlError(_) => "BrowseUrlError",
+            Error::NoBrowseUrl(_) => "NoBrowseUrl",
+            Error::RegexError(_) => "RegexError",
+            Error::IoError(_) => "IoError",
+            Error::NotCachedError(_) => "NotCachedError",
+            Error::StatusError(..) => "StatusError",
+            Error::ProxyError(_) => "ProxyError",
+            Error::CassetteError(_) => "CassetteError",
+            Error::InvalidSelector(_) => "InvalidSelector",
+            Error::LayoutChanged(..) => "LayoutChanged",
+            Error::PageOutOfRange(..) => "PageOutOfRange",
+            Error::InvalidImdbId(_) => "InvalidImdbId",
+            Error::MovieNotFound(_) => "MovieNotFound",
+            Error::InvalidTorrent(_) => "InvalidTorrent",
+            Error::TrackerError(_) => "TrackerError",
+            Error::ParseError(_) => "ParseError",
+        }
+    }
+}
+
 /// A convenient alias for `Result` with the crate's [`Error`] type.
 ///
 /// Defaults to `()` for the success type if not specified.
@@ -0,0 +1,202 @@
+//! [`serde`] support for the public model and filter types, behind the `serde` feature.
+//!
+//! Enums are serialized as the same strings the website uses (e.g. `"1080p.x265"`,
+//! `"sci-fi"`, `"1970-1979"`), so stored values stay readable and stable across
+//! releases. [`Response`] is wrapped with a schema version.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+
+use crate::{
+    Codec, Direction, Genre, Movie, OrderBy, Page, Quality, Rating, Resolution, Response, Source,
+    Year,
+};
+
+/// Implements `Serialize` and `Deserialize` for a type represented as a string.
+macro_rules! string_serde {
+    ($type:ty, $to_string:expr, $from_str:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&($to_string)(self))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                ($from_str)(value.as_str()).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+string_serde!(Genre, |value| <&str>::from(value).to_string(), |value| {
+    Genre::from_slug(value).ok_or_else(|| format!("Invalid value for Genre: {value}"))
+});
+string_serde!(Quality, Quality::to_string, Quality::try_from);
+string_serde!(
+    Resolution,
+    |value| <&str>::from(value).to_string(),
+    Resolution::try_from
+);
+string_serde!(
+    Codec,
+    |value| <&str>::from(value).to_string(),
+    Codec::try_from
+);
+string_serde!(
+    Source,
+    |value| <&str>::from(value).to_string(),
+    Source::try_from
+);
+string_serde!(
+    Rating,
+    |value| <&str>::from(value).to_string(),
+    Rating::try_from
+);
+string_serde!(Year, String::from, Year::try_from);
+string_serde!(
+    OrderBy,
+    |value| <&str>::from(value).to_string(),
+    OrderBy::try_from
+);
+string_serde!(
+    Direction,
+    |value| <&str>::from(value).to_string(),
+    Direction::try_from
+);
+
+/// Serialized form of a [`Response`], tagged with its schema version.
+#[derive(Serialize)]
+struct VersionedResponse<'a> {
+    version: u32,
+    page: &'a Page,
+    movies: &'a [Movie],
+}
+
+/// Owned counterpart of [`VersionedResponse`] used when deserializing.
+#[derive(Deserialize)]
+struct OwnedVersionedResponse {
+    version: u32,
+    page: Page,
+    movies: Vec<Movie>,
+}
+
+impl Serialize for Response {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VersionedResponse {
+            version: Response::SCHEMA_VERSION,
+            page: &self.page,
+            movies: &self.movies,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Response {
+    /// Deserializes a `Response`, rejecting schema versions this release does not know.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let response = OwnedVersionedResponse::deserialize(deserializer)?;
+        if response.version != Response::SCHEMA_VERSION {
+            return Err(D::Error::custom(format!(
+                "Unsupported Response schema version {} (expected {})",
+                response.version,
+                Response::SCHEMA_VERSION
+            )));
+        }
+
+        Ok(Response {
+            page: response.page,
+            movies: response.movies,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Codec, Direction, Filters, Genre, Movie, OrderBy, Page, Quality, Rating, Resolution,
+        Response, Source, Year,
+    };
+
+    #[test]
+    fn test_enum_string_representations() {
+        let to_json = |value: serde_json::Value| value.to_string();
+
+        assert_eq!(to_json(serde_json::json!(Genre::SciFi)), r#""sci-fi""#);
+        assert_eq!(
+            to_json(serde_json::json!(
+                Quality::new(Resolution::P1080)
+                    .source(Source::Web)
+                    .codec(Codec::X265)
+            )),
+            r#""1080p.WEB.x265""#
+        );
+        assert_eq!(to_json(serde_json::json!(Rating::Seven)), r#""7""#);
+        assert_eq!(
+            to_json(serde_json::json!(Year::Range1970to1979)),
+            r#""1970-1979""#
+        );
+        assert_eq!(to_json(serde_json::json!(Year::Equal(1972))), r#""1972""#);
+        assert_eq!(to_json(serde_json::json!(OrderBy::Seeds)), r#""seeds""#);
+        assert_eq!(to_json(serde_json::json!(Direction::Ascending)), r#""asc""#);
+
+        assert!(serde_json::from_str::<Genre>(r#""cooking""#).is_err());
+        assert!(serde_json::from_str::<Quality>(r#""1440p""#).is_err());
+    }
+
+    #[test]
+    fn test_filter_round_trip() {
+        let filter = Filters::default()
+            .quality(Quality::new(Resolution::P1080).codec(Codec::X265))
+            .genre(Genre::FilmNoir)
+            .rating(Rating::Eight)
+            .year(Year::Range(1941, 1958))
+            .order_by(OrderBy::Latest)
+            .direction(Direction::Ascending)
+            .language("en")
+            .page(3)
+            .build();
+
+        let json = serde_json::to_string(&filter).unwrap();
+        assert_eq!(
+            serde_json::from_str::<crate::Filter>(&json).unwrap(),
+            filter
+        );
+    }
+
+    #[test]
+    fn test_response_schema_version() {
+        let response = Response {
+            page: Page {
+                current: 1,
+                of: 1,
+                total: 1,
+                next: None,
+                previous: None,
+            },
+            movies: vec![Movie::new(
+                "The Godfather".to_string(),
+                1972,
+                9.2,
+                vec![Genre::Crime, Genre::Drama],
+                "https://yts.lt/assets/images/movies/The_Godfather_1972/medium-cover.jpg"
+                    .to_string(),
+                "https://yts.lt/movies/the-godfather-1972".to_string(),
+            )],
+        };
+
+        let mut json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["version"], Response::SCHEMA_VERSION);
+        assert_eq!(
+            json["movies"][0]["genres"],
+            serde_json::json!(["crime", "drama"])
+        );
+
+        let parsed: Response = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(parsed.movies[0].name, "The Godfather");
+        assert_eq!(parsed.movies[0].link, response.movies[0].link);
+
+        json["version"] = serde_json::json!(Response::SCHEMA_VERSION + 1);
+        assert!(serde_json::from_value::<Response>(json).is_err());
+    }
+}
@@ -0,0 +1,794 @@
+//! Local stand-in for the YTS website, behind the `test-util` feature.
+//!
+//! [`FakeYts`] is an in-process HTTP server serving browse and movie pages rendered from
+//! [`Movie`] and [`Torrent`] values, so code built on this crate can be tested without the
+//! network. Routes can also answer with an error status, a delay, or a bot challenge page.
+//! [`FakeTracker`] answers tracker scrapes over UDP and HTTP with registered swarm counts.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use yts_movies::{Filters, Genre, Quality, Resolution, test_util};
+//!
+//! let server = test_util::FakeYts::start().unwrap();
+//! let movie = test_util::movie("The Godfather", 1972, 9.2, vec![Genre::Crime, Genre::Drama]);
+//! let torrent = test_util::torrent(Quality::new(Resolution::P1080), "2.43 GB", "2 hr 55 min", 31, 512);
+//!
+//! server.browse("godfather", &Filters::default().build(), 1, &[&movie]);
+//! server.movie(&movie, &[torrent]);
+//!
+//! # #[cfg(feature = "async")]
+//! let yts = yts_movies::Yts::new(server.host(), Duration::from_secs(5));
+//! ```
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, UdpSocket},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::{Filter, Genre, InfoHash, Movie, Quality, SwarmHealth, Torrent};
+
+/// Body served by [`FakeYts::challenge`], modelled on a bot-protection interstitial.
+const CHALLENGE: &str = "<!DOCTYPE html><html><head><title>Just a moment...</title></head>\
+<body><div id=\"challenge-running\">Checking your browser before accessing the site.</div>\
+</body></html>";
+
+/// In-process fake YTS server listening on a local port.
+///
+/// Unregistered paths answer `404 Not Found`. The server stops when dropped.
+#[derive(Debug)]
+pub struct FakeYts {
+    /// Base URL of the server, e.g. `http://127.0.0.1:40123`.
+    host: String,
+    /// Address the server listens on.
+    addr: SocketAddr,
+    /// Registered responses by request target.
+    routes: Arc<Mutex<HashMap<String, Route>>>,
+    /// Request targets received, in order.
+    requests: Arc<Mutex<Vec<String>>>,
+    /// Set when the server is dropped.
+    shutdown: Arc<AtomicBool>,
+    /// Accept loop thread.
+    thread: Option<JoinHandle<()>>,
+}
+
+/// In-process fake BitTorrent tracker answering scrapes over UDP, as described by BEP 15,
+/// and over HTTP.
+///
+/// Torrents without a registered swarm are answered with an error. UDP requests can be
+/// lost or answered after a stray packet, to exercise retransmission. The tracker stops
+/// when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use yts_movies::{InfoHash, SwarmHealth, test_util};
+///
+/// let tracker = test_util::FakeTracker::start().unwrap();
+/// let info_hash = InfoHash::from([7; 20]);
+/// tracker.swarm(
+///     info_hash,
+///     SwarmHealth {
+///         seeders: 120,
+///         leechers: 8,
+///         completed: 4500,
+///     },
+/// );
+///
+/// let trackers = [tracker.udp_url(), tracker.http_url()];
+/// ```
+#[derive(Debug)]
+pub struct FakeTracker {
+    /// Address the UDP tracker listens on.
+    udp: SocketAddr,
+    /// Address the HTTP tracker listens on.
+    http: SocketAddr,
+    /// Registered swarms by info hash.
+    swarms: Arc<Mutex<HashMap<InfoHash, SwarmHealth>>>,
+    /// Number of upcoming UDP requests to ignore.
+    lost: Arc<AtomicUsize>,
+    /// Whether UDP answers are preceded by a stray packet.
+    strays: Arc<AtomicBool>,
+    /// Set when the tracker is dropped.
+    shutdown: Arc<AtomicBool>,
+    /// UDP and HTTP server threads.
+    threads: Vec<JoinHandle<()>>,
+}
+
+/// A registered response.
+#[derive(Debug, Clone)]
+struct Route {
+    /// HTTP status code.
+    status: u16,
+    /// Response body.
+    body: String,
+    /// How long to wait before answering.
+    delay: Duration,
+}
+
+impl FakeYts {
+    /// Starts a server on a free local port.
+    ///
+    /// # Errors
+    /// Returns an error if no local port can be bound.
+    pub fn start() -> crate::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+
+        let routes: Arc<Mutex<HashMap<String, Route>>> = Arc::default();
+        let requests: Arc<Mutex<Vec<String>>> = Arc::default();
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let routes = Arc::clone(&routes);
+            let requests = Arc::clone(&requests);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+
+                    let routes = Arc::clone(&routes);
+                    let requests = Arc::clone(&requests);
+                    std::thread::spawn(move || {
+                        let _ = serve(stream, &routes, &requests);
+                    });
+                }
+            })
+        };
+
+        Ok(Self {
+            host: format!("http://{addr}"),
+            addr,
+            routes,
+            requests,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns the base URL to pass to `Yts::new`.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the request targets received so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        lock(&self.requests).clone()
+    }
+
+    /// Serves a browse page listing `movies` for a search of `query` with `filter`.
+    ///
+    /// `total` is the number of movies reported in the page header, which drives pagination.
+    /// Movie cards link to pages on this server; see [`FakeYts::movie`].
+    ///
+    /// # Panics
+    /// Panics if `filter` has a year range the site cannot serve.
+    pub fn browse(&self, query: &str, filter: &Filter, total: u32, movies: &[&Movie]) {
+        let target = self
+            .browse_target(query, filter)
+            .expect("filter should build a browse url");
+        let path = target.split('?').next().unwrap_or_default();
+        let body = render_browse(&self.host, path, filter.page.max(1), total, movies);
+        self.page(&target, 200, body);
+    }
+
+    /// Serves the page of `movie` listing `torrents`.
+    ///
+    /// Torrents without a link are given one on this server.
+    pub fn movie(&self, movie: &Movie, torrents: &[Torrent]) {
+        let target = format!("/movies/{}", slug(movie));
+        self.page(
+            &target,
+            200,
+            render_movie(&self.host, movie, None, torrents),
+        );
+    }
+
+    /// Serves the page of `movie` like [`movie`](Self::movie), linking to the IMDb title
+    /// `imdb_id`, e.g. `tt0068646`.
+    pub fn imdb_movie(&self, movie: &Movie, imdb_id: &str, torrents: &[Torrent]) {
+        let target = format!("/movies/{}", slug(movie));
+        let body = render_movie(&self.host, movie, Some(imdb_id), torrents);
+        self.page(&target, 200, body);
+    }
+
+    /// Serves `body` with `status` at `target`, a path with an optional query string.
+    pub fn page(&self, target: &str, status: u16, body: impl Into<String>) {
+        lock(&self.routes).insert(
+            target.to_string(),
+            Route {
+                status,
+                body: body.into(),
+                delay: Duration::ZERO,
+            },
+        );
+    }
+
+    /// Answers `target` with `status` and an empty body.
+    pub fn status(&self, target: &str, status: u16) {
+        self.page(target, status, "");
+    }
+
+    /// Answers `target` with a `403 Forbidden` bot challenge page.
+    pub fn challenge(&self, target: &str) {
+        self.page(target, 403, CHALLENGE);
+    }
+
+    /// Delays the answer to `target` by `delay`, keeping its registered response.
+    ///
+    /// An unregistered target is delayed and then answered `404 Not Found`.
+    pub fn delay(&self, target: &str, delay: Duration) {
+        lock(&self.routes)
+            .entry(target.to_string())
+            .or_insert_with(|| Route {
+                status: 404,
+                body: String::new(),
+                delay,
+            })
+            .delay = delay;
+    }
+
+    /// Returns the browse target for `query` and `filter`, as requested by the clients.
+    ///
+    /// # Errors
+    /// Returns an error if `filter` has a year range the site cannot serve.
+    pub fn browse_target(&self, query: &str, filter: &Filter) -> crate::Result<String> {
+        let url = filter.browse_url(&self.host, query)?;
+        Ok(url[self.host.len()..].to_string())
+    }
+}
+
+impl Drop for FakeYts {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl FakeTracker {
+    /// Connection id handed out to UDP clients.
+    const CONNECTION_ID: u64 = 0x5eed;
+
+    /// Starts a tracker on free local UDP and TCP ports.
+    ///
+    /// # Errors
+    /// Returns an error if no local port can be bound.
+    pub fn start() -> crate::Result<Self> {
+        let socket = UdpSocket::bind("127.0.0.1:0")?;
+        socket.set_read_timeout(Some(Duration::from_millis(50)))?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let (udp, http) = (socket.local_addr()?, listener.local_addr()?);
+
+        let swarms: Arc<Mutex<HashMap<InfoHash, SwarmHealth>>> = Arc::default();
+        let lost = Arc::new(AtomicUsize::new(0));
+        let strays = Arc::new(AtomicBool::new(false));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let udp_thread = {
+            let swarms = Arc::clone(&swarms);
+            let lost = Arc::clone(&lost);
+            let strays = Arc::clone(&strays);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                let mut buffer = [0; 512];
+                while !shutdown.load(Ordering::SeqCst) {
+                    let Ok((length, peer)) = socket.recv_from(&mut buffer) else {
+                        continue;
+                    };
+                    let take = |count: usize| count.checked_sub(1);
+                    if lost
+                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, take)
+                        .is_ok()
+                    {
+                        continue;
+                    }
+                    let Some(response) = answer_udp(&buffer[..length], &swarms) else {
+                        continue;
+                    };
+                    if strays.load(Ordering::SeqCst) {
+                        let mut stray = response.clone();
+                        stray[4..8].iter_mut().for_each(|byte| *byte = !*byte);
+                        let _ = socket.send_to(&stray, peer);
+                    }
+                    let _ = socket.send_to(&response, peer);
+                }
+            })
+        };
+        let http_thread = {
+            let swarms = Arc::clone(&swarms);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let _ = serve_scrape(stream, &swarms);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            udp,
+            http,
+            swarms,
+            lost,
+            strays,
+            shutdown,
+            threads: vec![udp_thread, http_thread],
+        })
+    }
+
+    /// Returns the announce URL of the UDP tracker, e.g. `udp://127.0.0.1:40123/announce`.
+    pub fn udp_url(&self) -> String {
+        format!("udp://{}/announce", self.udp)
+    }
+
+    /// Returns the announce URL of the HTTP tracker, e.g. `http://127.0.0.1:40124/announce`.
+    pub fn http_url(&self) -> String {
+        format!("http://{}/announce", self.http)
+    }
+
+    /// Reports `health` for the torrent with `info_hash`.
+    pub fn swarm(&self, info_hash: InfoHash, health: SwarmHealth) {
+        lock(&self.swarms).insert(info_hash, health);
+    }
+
+    /// Ignores the next `count` UDP requests, as if the packets were lost.
+    pub fn lose_udp(&self, count: usize) {
+        self.lost.store(count, Ordering::SeqCst);
+    }
+
+    /// Sends a stray packet before each UDP answer, answering another transaction as a
+    /// late answer to an earlier request would.
+    pub fn stray_udp(&self, enabled: bool) {
+        self.strays.store(enabled, Ordering::SeqCst);
+    }
+}
+
+impl Drop for FakeTracker {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag; the UDP loop polls it.
+        let _ = TcpStream::connect(self.http);
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Creates a movie for the fake server. Its link is filled in when rendered.
+pub fn movie(name: &str, year: u32, rating: f32, genres: Vec<Genre>) -> Movie {
+    Movie::new(
+        name.to_string(),
+        year,
+        rating,
+        genres,
+        String::new(),
+        String::new(),
+    )
+}
+
+/// Creates an English torrent for the fake server. Its link is filled in when rendered.
+///
+/// # Parameters
+/// - `size`: Size as shown on the site, e.g. `1.54 GB`.
+/// - `runtime`: Runtime as shown on the site, e.g. `2 hr 55 min`.
+pub fn torrent(quality: Quality, size: &str, runtime: &str, peers: u32, seeds: u32) -> Torrent {
+    Torrent::new(
+        quality,
+        size,
+        "English 2.0",
+        runtime,
+        &format!("{peers} / {seeds}"),
+        String::new(),
+    )
+}
+
+/// Reads one request from `stream` and writes the registered response.
+fn serve(
+    mut stream: TcpStream,
+    routes: &Mutex<HashMap<String, Route>>,
+    requests: &Mutex<Vec<String>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let Some(mut target) = request_line.split_whitespace().nth(1) else {
+        return Ok(());
+    };
+    // Requests forwarded by an HTTP proxy carry the absolute URL.
+    if let Some(url) = target.strip_prefix("http://") {
+        target = url.find('/').map_or("/", |i| &url[i..]);
+    }
+
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    lock(requests).push(target.to_string());
+    let route = lock(routes).get(target).cloned().unwrap_or(Route {
+        status: 404,
+        body: String::new(),
+        delay: Duration::ZERO,
+    });
+
+    std::thread::sleep(route.delay);
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        route.status,
+        reason(route.status),
+        route.body.len(),
+        route.body
+    )?;
+    stream.flush()
+}
+
+/// Returns the answer to a UDP tracker request, or `None` to ignore it.
+fn answer_udp(request: &[u8], swarms: &Mutex<HashMap<InfoHash, SwarmHealth>>) -> Option<Vec<u8>> {
+    let word = |at: usize| -> Option<[u8; 4]> { request.get(at..at + 4)?.try_into().ok() };
+    let (action, transaction_id) = (u32::from_be_bytes(word(8)?), word(12)?);
+
+    let mut response = Vec::new();
+    match action {
+        0 if request[..8] == 0x41727101980u64.to_be_bytes() => {
+            response.extend(0u32.to_be_bytes());
+            response.extend(transaction_id);
+            response.extend(FakeTracker::CONNECTION_ID.to_be_bytes());
+        }
+        2 if request[..8] == FakeTracker::CONNECTION_ID.to_be_bytes() => {
+            let info_hash: [u8; 20] = request.get(16..36)?.try_into().ok()?;
+            match lock(swarms).get(&InfoHash::from(info_hash)) {
+                Some(health) => {
+                    response.extend(2u32.to_be_bytes());
+                    response.extend(transaction_id);
+                    response.extend(health.seeders.to_be_bytes());
+                    response.extend(health.completed.to_be_bytes());
+                    response.extend(health.leechers.to_be_bytes());
+                }
+                None => {
+                    response.extend(3u32.to_be_bytes());
+                    response.extend(transaction_id);
+                    response.extend(b"unknown torrent");
+                }
+            }
+        }
+        _ => return None,
+    }
+    Some(response)
+}
+
+/// Reads one HTTP scrape request from `stream` and writes the bencoded answer.
+fn serve_scrape(
+    mut stream: TcpStream,
+    swarms: &Mutex<HashMap<InfoHash, SwarmHealth>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    let info_hash = target
+        .strip_prefix("/scrape?info_hash=")
+        .and_then(percent_decode)
+        .and_then(|hash| <[u8; 20]>::try_from(hash).ok())
+        .map(InfoHash::from);
+    let (status, body) = match info_hash {
+        Some(info_hash) => match lock(swarms).get(&info_hash) {
+            Some(health) => {
+                let mut body = b"d5:filesd20:".to_vec();
+                body.extend(info_hash.as_bytes());
+                body.extend(
+                    format!(
+                        "d8:completei{}e10:downloadedi{}e10:incompletei{}eeee",
+                        health.seeders, health.completed, health.leechers
+                    )
+                    .bytes(),
+                );
+                (200, body)
+            }
+            None => (200, b"d14:failure reason15:unknown torrente".to_vec()),
+        },
+        None => (404, Vec::new()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        reason(status),
+        body.len()
+    )?;
+    stream.write_all(&body)?;
+    stream.flush()
+}
+
+/// Decodes a percent-encoded query value, or returns `None` if malformed.
+fn percent_decode(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = value.bytes();
+    let mut decoded = Vec::new();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'%' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            byte => decoded.push(byte),
+        }
+    }
+    Some(decoded)
+}
+
+/// Renders a browse page in the site's markup, with a pagination widget linking to other
+/// pages of `path` when `total` spans several pages.
+fn render_browse(host: &str, path: &str, page: u32, total: u32, movies: &[&Movie]) -> String {
+    let mut cards = String::new();
+    for movie in movies {
+        let link = format!("{host}/movies/{}", slug(movie));
+        let genres = movie
+            .genres
+            .iter()
+            .map(|genre| format!("<h4>{}</h4>", genre_name(genre)))
+            .collect::<String>();
+
+        cards.push_str(&format!(
+            r#"<div class="browse-movie-wrap"><a href="{link}" class="browse-movie-link"><figure><img class="img-responsive" src="{image}"><figcaption><span class="icon-star"></span><h4 class="rating">{rating:.1} / 10</h4>{genres}<span class="button-green-download2-big">View Details</span></figcaption></figure></a><div class="browse-movie-bottom"><a href="{link}" class="browse-movie-title">{name}</a><div class="browse-movie-year">{year}</div></div></div>"#,
+            image = escape(&movie.image),
+            rating = movie.rating,
+            name = escape(&movie.name),
+            year = movie.year,
+        ));
+    }
+
+    let last = total.div_ceil(20);
+    let mut pagination = String::new();
+    if last > 1 {
+        let link = |target: u32, text: &str| match target {
+            1 => format!(r#"<li><a href="{path}">{text}</a></li>"#),
+            _ => format!(r#"<li><a href="{path}?page={target}">{text}</a></li>"#),
+        };
+        if page > 1 {
+            pagination.push_str(&link(1, "&laquo; First"));
+            pagination.push_str(&link(page - 1, "&laquo; Previous"));
+        }
+        for target in page.saturating_sub(2).max(1)..=(page + 2).min(last) {
+            if target == page {
+                pagination.push_str(&format!(
+                    r#"<li><a href="javascript:void(0)" class="current">{page}</a></li>"#
+                ));
+            } else {
+                pagination.push_str(&link(target, &target.to_string()));
+            }
+        }
+        if page < last {
+            pagination.push_str(&link(page + 1, "Next &raquo;"));
+            pagination.push_str(&link(last, "Last &raquo;"));
+        }
+        pagination = format!(r#"<ul class="tsc_pagination">{pagination}</ul>"#);
+    }
+
+    format!(
+        r#"<!DOCTYPE html><html><head><title>Browse Movies</title></head><body><div class="container"><h2><b>{total}</b> YIFY Movies found</h2></div>{pagination}<section><div class="row">{cards}</div></section>{pagination}</body></html>"#
+    )
+}
+
+/// Renders a movie page in the site's markup.
+fn render_movie(host: &str, movie: &Movie, imdb_id: Option<&str>, torrents: &[Torrent]) -> String {
+    let imdb = imdb_id.map_or(String::new(), |id| {
+        format!(
+            r#"<div class="rating-row"><a href="https://www.imdb.com/title/{}/" title="IMDb Rating">IMDb</a></div>"#,
+            escape(id)
+        )
+    });
+    let mut links = String::new();
+    let mut qualities = String::new();
+    let mut specs = String::new();
+    for (i, torrent) in torrents.iter().enumerate() {
+        let link = if torrent.link.is_empty() {
+            format!("{host}/torrent/download/{}-{i}", slug(movie))
+        } else {
+            escape(&torrent.link)
+        };
+
+        links.push_str(&format!(
+            r#"<a href="{link}" rel="nofollow">{}</a> "#,
+            torrent.quality
+        ));
+        qualities.push_str(&format!(
+            r#"<span class="tech-quality">{}</span>"#,
+            torrent.quality
+        ));
+        specs.push_str(&format!(
+            r#"<div class="tech-spec-info"><div class="row"><div class="col-xs-4"><span class="icon-folder"></span> {size}</div><div class="col-xs-4"><span class="icon-size-fullscreen"></span> {resolution}</div><div class="col-xs-4"><span class="icon-volume-medium"></span> {language}</div></div><div class="row"><div class="col-xs-4"><span class="icon-film"></span> 23.976 fps</div><div class="col-xs-4"><span class="icon-clock"></span> {runtime}</div></div><div class="row"><div class="col-xs-4">Subtitles</div><div class="col-xs-4">P/S</div><div class="col-xs-4">{peers_seeds}</div></div></div>"#,
+            size = escape(&torrent.size),
+            resolution = <&str>::from(&torrent.quality.resolution),
+            language = escape(&torrent.language),
+            runtime = escape(&torrent.runtime),
+            peers_seeds = escape(&torrent.peers_seeds),
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html><html><head><title>{name} ({year})</title></head><body><div id="movie-info"><h1>{name}</h1><h2>{year}</h2>{imdb}<p class="hidden-xs hidden-sm"><em>Available in:</em> {links}</p></div><div id="movie-tech-specs"><div class="tech-quality-wrap">{qualities}</div>{specs}</div></body></html>"#,
+        name = escape(&movie.name),
+        year = movie.year,
+    )
+}
+
+/// Returns the site slug of `movie`, e.g. `the-godfather-1972`.
+fn slug(movie: &Movie) -> String {
+    let name = movie
+        .name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    format!("{name}-{}", movie.year)
+}
+
+/// Returns the genre as displayed on the site, e.g. `Sci-Fi`.
+fn genre_name(genre: &Genre) -> String {
+    let slug: &str = genre.into();
+    slug.split('-')
+        .map(|part| match part {
+            "tv" => "TV".to_string(),
+            part => part[..1].to_uppercase() + &part[1..],
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Escapes text for use in HTML content and attributes.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Returns the reason phrase of `status`.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        304 => "Not Modified",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+/// Locks `mutex`, recovering the data if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(all(test, feature = "async"))]
+mod test {
+    use std::time::Duration;
+
+    use crate::{Codec, Filters, Genre, Quality, Resolution, Source, Yts};
+
+    use super::{FakeYts, movie, torrent};
+
+    #[tokio::test]
+    async fn test_fake_yts_search_and_torrents() {
+        let server = FakeYts::start().unwrap();
+        let godfather = movie("The Godfather", 1972, 9.2, vec![Genre::Crime, Genre::Drama]);
+        let sequel = movie("The Godfather Part II", 1974, 9.0, vec![Genre::SciFi]);
+        let filter = Filters::default().page(2).build();
+        server.browse("godfather", &filter, 42, &[&godfather, &sequel]);
+        server.movie(
+            &godfather,
+            &[
+                torrent(
+                    Quality::new(Resolution::P720).source(Source::BluRay),
+                    "1.15 GB",
+                    "2 hr 55 min",
+                    12,
+                    340,
+                ),
+                torrent(
+                    Quality::new(Resolution::P1080).codec(Codec::X265),
+                    "2.43 GB",
+                    "2 hr 55 min",
+                    31,
+                    512,
+                ),
+            ],
+        );
+
+        let yts = Yts::new(server.host(), Duration::from_secs(5));
+        let response = yts.search_with_filter("godfather", filter).await.unwrap();
+        assert_eq!((response.page.current, response.page.of), (2, 3));
+        assert_eq!(
+            (response.page.previous, response.page.next),
+            (Some(1), Some(3))
+        );
+        assert_eq!(response.movies.len(), 2);
+        assert_eq!(response.movies[0].name, "The Godfather");
+        assert_eq!(response.movies[1].genres, [Genre::SciFi]);
+
+        let torrents = yts.torrents(&response.movies[0]).await.unwrap();
+        assert_eq!(torrents.len(), 2);
+        assert_eq!(
+            torrents[1].quality,
+            Quality::new(Resolution::P1080).codec(Codec::X265)
+        );
+        assert_eq!(torrents[1].seeds(), Some(512));
+        assert!(torrents[0].link.starts_with(server.host()));
+
+        assert_eq!(
+            server.requests(),
+            [
+                "/browse-movies/godfather/all/all/0/latest/0/all?page=2",
+                "/movies/the-godfather-1972"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fake_yts_failures() {
+        let server = FakeYts::start().unwrap();
+        let filter = Filters::default().build();
+        let target = server.browse_target("godfather", &filter).unwrap();
+        let yts = Yts::new(server.host(), Duration::from_millis(200));
+
+        assert!(matches!(
+            yts.search("godfather").await,
+            Err(crate::Error::StatusError(404, _))
+        ));
+
+        server.challenge(&target);
+        assert!(matches!(
+            yts.search("godfather").await,
+            Err(crate::Error::StatusError(403, _))
+        ));
+
+        server.status(&target, 503);
+        assert!(matches!(
+            yts.search("godfather").await,
+            Err(crate::Error::StatusError(503, _))
+        ));
+
+        server.browse("godfather", &filter, 0, &[]);
+        server.delay(&target, Duration::from_secs(2));
+        assert!(matches!(
+            yts.search("godfather").await,
+            Err(crate::Error::ReqwestError(_))
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+//! Internal helpers emitting [`tracing`] data when the `tracing` feature is enabled.
+//!
+//! Without the feature the macros expand to nothing beyond evaluating their values, so
+//! call sites need no `cfg` attributes of their own.
+
+/// Records fields on the current span, e.g. `record!(status = 200u16, bytes = body.len())`.
+macro_rules! record {
+    ($($field:ident = $value:expr),+ $(,)?) => {{
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            $(span.record(stringify!($field), $value);)+
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            $(let _ = &$value;)+
+        }
+    }};
+}
+
+/// Emits a warning event, with the same syntax as [`tracing::warn!`].
+macro_rules! warning {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)+);
+    }};
+}
+
+pub(crate) use {record, warning};